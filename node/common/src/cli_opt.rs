@@ -20,6 +20,37 @@ impl FromStr for EthApi {
 	}
 }
 
+/// How blocks are authored when the dev node seals them on demand.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal a block only when asked to through `engine_createBlock`.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"instant" => Self::Instant,
+			"manual" => Self::Manual,
+			s => {
+				let millis = s.parse::<u64>().map_err(|_| {
+					format!("`{}` is not `instant`, `manual` or an interval in milliseconds", s)
+				})?;
+				if millis == 0 {
+					return Err("the sealing interval must be at least 1 millisecond".into())
+				}
+				Self::Interval(millis)
+			},
+		})
+	}
+}
+
 /// Available frontier backend types.
 #[derive(Debug, Copy, Clone, Default, clap::ValueEnum)]
 pub enum BackendType {
@@ -31,6 +62,7 @@ pub enum BackendType {
 }
 
 /// Defines the frontier backend configuration.
+#[derive(Clone)]
 pub enum BackendTypeConfig {
 	KeyValue,
 	Sql { pool_size: u32, num_ops_timeout: u32, thread_count: u32, cache_size: u64 },
@@ -42,6 +74,7 @@ impl Default for BackendTypeConfig {
 	}
 }
 
+#[derive(Clone)]
 pub struct RpcConfig {
 	pub ethapi: Vec<EthApi>,
 	pub ethapi_max_permits: u32,
@@ -55,3 +88,17 @@ pub struct RpcConfig {
 	pub tracing_raw_max_memory_usage: usize,
 	pub frontier_backend_type: BackendTypeConfig,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sealing_parses_modes_and_intervals() {
+		assert_eq!(Sealing::from_str("instant"), Ok(Sealing::Instant));
+		assert_eq!(Sealing::from_str("manual"), Ok(Sealing::Manual));
+		assert_eq!(Sealing::from_str("6000"), Ok(Sealing::Interval(6000)));
+		assert!(Sealing::from_str("0").is_err());
+		assert!(Sealing::from_str("often").is_err());
+	}
+}
@@ -0,0 +1,325 @@
+//! Maintenance of the Frontier mapping database, from Ethereum block and transaction hashes to
+//! Substrate blocks, without resyncing the chain.
+//!
+//! Mappings are rebuilt from the Ethereum blocks the runtime keeps in the Substrate state, so
+//! the node must run as an archive node for old blocks to be reindexed.
+
+use crate::cli_opt::BackendType;
+
+use std::{
+	io::{BufRead, BufReader, BufWriter, Write},
+	path::PathBuf,
+	sync::Arc,
+};
+
+use fc_rpc::{StorageOverride, StorageOverrideHandler};
+use sc_client_api::{
+	backend::{Backend, StateBackend},
+	AuxStore, StorageProvider,
+};
+use serde::{Deserialize, Serialize};
+
+use bp_core::{Block, BlockNumber};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::H256;
+use sp_runtime::traits::BlakeTwo256;
+
+/// A range of blocks, by number.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BlockRange {
+	/// The first block of the range.
+	#[arg(long, default_value_t = 0)]
+	pub from: BlockNumber,
+
+	/// The last block of the range, the best block by default.
+	#[arg(long)]
+	pub to: Option<BlockNumber>,
+}
+
+/// Operations on the Frontier mapping database.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum FrontierDbOp {
+	/// Export the mappings of a range of blocks, as JSON lines.
+	Export {
+		/// The file to export to.
+		#[arg(long)]
+		output: PathBuf,
+
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		range: BlockRange,
+	},
+	/// Import mappings created by `export`. Only supported by the key-value backend.
+	Import {
+		/// The file to import from.
+		#[arg(long)]
+		input: PathBuf,
+	},
+	/// Rebuild the mappings of a range of blocks from the Substrate database.
+	Reindex {
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		range: BlockRange,
+	},
+	/// Check the mappings of a range of blocks against the Substrate database.
+	Verify {
+		#[allow(missing_docs)]
+		#[clap(flatten)]
+		range: BlockRange,
+	},
+	/// Rebuild the mappings of every block in a backend of another type.
+	///
+	/// Run the node with `--frontier-backend-type` set to the new type afterwards.
+	Migrate {
+		/// The backend type to migrate to.
+		#[arg(long, value_enum)]
+		to: BackendType,
+	},
+}
+
+/// The mappings of one block, as exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockMapping {
+	pub block_number: BlockNumber,
+	pub block_hash: H256,
+	pub ethereum_block_hash: H256,
+	pub ethereum_transaction_hashes: Vec<H256>,
+}
+
+/// Run `op` on `backend`. `open_backend` opens the backend of another type to migrate to.
+pub async fn run<C, BE>(
+	op: &FrontierDbOp,
+	client: Arc<C>,
+	backend: fc_db::Backend<Block, C>,
+	open_backend: impl FnOnce(BackendType) -> Result<fc_db::Backend<Block, C>, String>,
+) -> Result<(), String>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let storage_override = StorageOverrideHandler::<Block, _, _>::new(client.clone());
+
+	match op {
+		FrontierDbOp::Export { output, range } => {
+			let file = std::fs::File::create(output).map_err(|e| e.to_string())?;
+			let mut writer = BufWriter::new(file);
+			let mut exported = 0;
+			for (number, hash) in block_hashes(&client, range)? {
+				if let Some(mapping) =
+					stored_mapping(&backend, &storage_override, number, hash).await?
+				{
+					serde_json::to_writer(&mut writer, &mapping).map_err(|e| e.to_string())?;
+					writer.write_all(b"\n").map_err(|e| e.to_string())?;
+					exported += 1;
+				}
+			}
+			writer.flush().map_err(|e| e.to_string())?;
+			log::info!("exported the mappings of {exported} blocks to {}", output.display());
+		},
+		FrontierDbOp::Import { input } => {
+			// The SQL backend also indexes logs, which are not exported.
+			let fc_db::Backend::KeyValue(kv) = &backend else {
+				return Err("only the key-value backend can import, reindex instead".into())
+			};
+			let file = std::fs::File::open(input).map_err(|e| e.to_string())?;
+			let mut imported = 0;
+			for line in BufReader::new(file).lines() {
+				let line = line.map_err(|e| e.to_string())?;
+				if line.trim().is_empty() {
+					continue;
+				}
+				let mapping: BlockMapping =
+					serde_json::from_str(&line).map_err(|e| e.to_string())?;
+				kv.mapping().write_hashes(fc_db::kv::MappingCommitment {
+					block_hash: mapping.block_hash,
+					ethereum_block_hash: mapping.ethereum_block_hash,
+					ethereum_transaction_hashes: mapping.ethereum_transaction_hashes,
+				})?;
+				imported += 1;
+			}
+			log::info!("imported the mappings of {imported} blocks");
+		},
+		FrontierDbOp::Reindex { range } => {
+			let indexed = reindex(&client, &backend, &storage_override, range).await?;
+			log::info!("reindexed {indexed} blocks");
+		},
+		FrontierDbOp::Verify { range } => {
+			let mut invalid = 0;
+			for (number, hash) in block_hashes(&client, range)? {
+				let Some(block) = storage_override.current_block(hash) else { continue };
+				let expected: Vec<H256> = block.transactions.iter().map(|t| t.hash()).collect();
+				let stored = stored_mapping(&backend, &storage_override, number, hash).await?;
+				if stored.map(|stored| stored.ethereum_transaction_hashes) != Some(expected) {
+					log::warn!("block #{number} {hash:?} has missing or wrong mappings");
+					invalid += 1;
+				}
+			}
+			if invalid > 0 {
+				return Err(format!(
+					"{invalid} blocks have missing or wrong mappings, \
+					run `frontier-db reindex` to fix them"
+				));
+			}
+			log::info!("all mappings are valid");
+		},
+		FrontierDbOp::Migrate { to } => {
+			let same_type = matches!(
+				(&backend, to),
+				(fc_db::Backend::KeyValue(_), BackendType::KeyValue) |
+					(fc_db::Backend::Sql(_), BackendType::Sql)
+			);
+			if same_type {
+				return Err("the backend is already of this type".into());
+			}
+			let target = open_backend(*to)?;
+			let range = BlockRange { from: 0, to: None };
+			let indexed = reindex(&client, &target, &storage_override, &range).await?;
+			log::info!("migrated the mappings of {indexed} blocks");
+		},
+	}
+
+	Ok(())
+}
+
+/// The canonical block hashes of `range`.
+fn block_hashes<C>(client: &C, range: &BlockRange) -> Result<Vec<(BlockNumber, H256)>, String>
+where
+	C: HeaderBackend<Block>,
+{
+	let to = range.to.unwrap_or_else(|| client.info().best_number);
+	(range.from..=to)
+		.map(|number| {
+			let hash = client
+				.hash(number)
+				.map_err(|e| e.to_string())?
+				.ok_or_else(|| format!("block #{number} not found"))?;
+			Ok((number, hash))
+		})
+		.collect()
+}
+
+/// The mappings of a block stored in `backend`, if there are any.
+async fn stored_mapping<C, BE>(
+	backend: &fc_db::Backend<Block, C>,
+	storage_override: &StorageOverrideHandler<Block, C, BE>,
+	number: BlockNumber,
+	hash: H256,
+) -> Result<Option<BlockMapping>, String>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	use fc_api::Backend as _;
+
+	let api: &dyn fc_api::Backend<Block> = match backend {
+		fc_db::Backend::KeyValue(kv) => kv.as_ref(),
+		fc_db::Backend::Sql(sql) => sql.as_ref(),
+	};
+	let Some(block) = storage_override.current_block(hash) else { return Ok(None) };
+	let ethereum_block_hash = block.header.hash();
+	let mapped = api.block_hash(&ethereum_block_hash).await?.unwrap_or_default();
+	if !mapped.contains(&hash) {
+		return Ok(None);
+	}
+
+	let mut ethereum_transaction_hashes = Vec::new();
+	for (index, transaction) in block.transactions.iter().enumerate() {
+		let transaction_hash = transaction.hash();
+		let metadata = api.transaction_metadata(&transaction_hash).await?;
+		if metadata
+			.iter()
+			.any(|m| m.substrate_block_hash == hash && m.ethereum_index == index as u32)
+		{
+			ethereum_transaction_hashes.push(transaction_hash);
+		}
+	}
+	Ok(Some(BlockMapping {
+		block_number: number,
+		block_hash: hash,
+		ethereum_block_hash,
+		ethereum_transaction_hashes,
+	}))
+}
+
+/// Rebuild the mappings of `range` in `backend`, returning the number of blocks indexed.
+async fn reindex<C, BE>(
+	client: &Arc<C>,
+	backend: &fc_db::Backend<Block, C>,
+	storage_override: &StorageOverrideHandler<Block, C, BE>,
+	range: &BlockRange,
+) -> Result<usize, String>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let hashes: Vec<_> =
+		block_hashes(client.as_ref(), range)?.into_iter().map(|(_, h)| h).collect();
+	for (i, hash) in hashes.iter().enumerate() {
+		index_block(client, backend, storage_override, *hash).await?;
+		if (i + 1) % 10_000 == 0 {
+			log::info!("reindexed {} of {} blocks", i + 1, hashes.len());
+		}
+	}
+	canonicalize(backend, &hashes).await?;
+	Ok(hashes.len())
+}
+
+/// Rebuild the mappings of a block from the Substrate database.
+async fn index_block<C, BE>(
+	client: &Arc<C>,
+	backend: &fc_db::Backend<Block, C>,
+	storage_override: &StorageOverrideHandler<Block, C, BE>,
+	hash: H256,
+) -> Result<(), String>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	match backend {
+		fc_db::Backend::KeyValue(kv) => match storage_override.current_block(hash) {
+			Some(block) => kv.mapping().write_hashes(fc_db::kv::MappingCommitment {
+				block_hash: hash,
+				ethereum_block_hash: block.header.hash(),
+				ethereum_transaction_hashes: block.transactions.iter().map(|t| t.hash()).collect(),
+			}),
+			None => kv.mapping().write_none(hash),
+		},
+		fc_db::Backend::Sql(sql) => {
+			sql.insert_block_metadata(client.clone(), hash)
+				.await
+				.map_err(|e| format!("failed to index block {hash:?}: {e:?}"))?;
+			sql.index_block_logs(hash).await;
+			Ok(())
+		},
+	}
+}
+
+/// Mark `hashes` as canonical. Only the SQL backend tracks it.
+async fn canonicalize<C>(backend: &fc_db::Backend<Block, C>, hashes: &[H256]) -> Result<(), String>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	if let fc_db::Backend::Sql(sql) = backend {
+		sql.canonicalize(&[], hashes)
+			.await
+			.map_err(|e| format!("failed to canonicalize blocks: {e:?}"))?;
+	}
+	Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod cli_opt;
+pub mod frontier_db;
 pub mod rpc;
 pub mod service;
 pub mod tracing;
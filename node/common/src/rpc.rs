@@ -126,15 +126,23 @@ pub struct TracingConfig {
 
 pub mod staking {
 	use codec::Encode;
+	use futures::{FutureExt, Stream, StreamExt};
 	use jsonrpsee::{
 		proc_macros::rpc,
 		types::{ErrorObject, ErrorObjectOwned},
+		PendingSubscriptionSink,
 	};
 	use pallet_staking::RewardDestination;
 	use pallet_staking_runtime_api::AccountId20 as AccountId;
-	use pallet_staking_runtime_api::StakingRpcApi;
-	use sp_api::{ApiError, ProvideRuntimeApi};
+	use pallet_staking_runtime_api::{
+		NominatorInfo, PageError, StakingEvent, StakingPage, StakingRpcApi, ValidatorInfo,
+		ValidatorOrder,
+	};
+	use sc_client_api::BlockchainEvents;
+	use sc_rpc::{utils::pipe_from_stream, SubscriptionTaskExecutor};
+	use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
 	use sp_blockchain::HeaderBackend;
+	use sp_runtime::traits::Block as BlockT;
 	use std::str::FromStr;
 	use std::sync::Arc;
 
@@ -147,6 +155,13 @@ pub mod staking {
 		Controller,
 		Account(String),
 		None,
+		AutoCompound,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawCompoundPolicy {
+		pub threshold: String,
+		pub stake: String,
 	}
 
 	#[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,6 +171,7 @@ pub mod staking {
 		pub total_staking: String,
 		pub active_staking: String,
 		pub rewrds_destination: RawRewardDestination,
+		pub auto_compound: Option<RawCompoundPolicy>,
 	}
 
 	#[derive(Debug, Clone, Serialize, Deserialize)]
@@ -170,15 +186,79 @@ pub mod staking {
 	}
 
 	#[derive(Debug, Clone, Serialize, Deserialize)]
-	pub struct RawProvider {
-		pub pid: String,
-		pub owner: String,
-		pub cap_pledge: String,
-		pub total_pledge: String,
-		pub devices_num: String,
-		pub total_punishment: String,
+	pub struct RawEraReward {
+		pub era: u32,
+		pub earned: String,
+		pub commission: String,
+		pub staking: String,
+		pub paid: String,
+		pub destination: Option<RawRewardDestination>,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawRewardHistory {
 		pub total_rewards: String,
-		pub unpaid_rewards: String,
+		pub paid_rewards: String,
+		pub eras: Vec<RawEraReward>,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub enum RawEraChange {
+		EraPayout { era: u32, validator_payout: String, remainder: String },
+		StakersElected,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawReward {
+		pub era: u32,
+		pub validator: String,
+		pub account: String,
+		pub commission: String,
+		pub staking: String,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawSlash {
+		pub staker: String,
+		pub amount: String,
+	}
+
+	#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	pub enum RawValidatorOrder {
+		Stake,
+		Storage,
+	}
+
+	/// One page of a staking list. `next_page` is passed as `page` to get the next one.
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawPage<T> {
+		pub items: Vec<T>,
+		pub next_page: Option<String>,
+	}
+
+	/// A staking event, along with the finalized block it was deposited in.
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawBlockEvent<T> {
+		pub block_hash: String,
+		pub event: T,
+	}
+
+	/// The result for one account of a batch query.
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawBatchItem<T> {
+		pub account: String,
+		pub value: Option<T>,
+		pub error: Option<String>,
+	}
+
+	impl<T> RawBatchItem<T> {
+		fn new(account: String, result: Result<T, Error>) -> Self {
+			match result {
+				Ok(value) => RawBatchItem { account, value: Some(value), error: None },
+				Err(e) => RawBatchItem { account, value: None, error: Some(e.to_string()) },
+			}
+		}
 	}
 
 	#[derive(Debug, thiserror::Error)]
@@ -190,10 +270,21 @@ pub mod staking {
 		ApiCallErr(ApiError),
 		#[error("no nominator storage for account")]
 		NoStorage,
+		#[error("page start is not in the list")]
+		InvalidPage,
+		#[error("not supported by the runtime at this block")]
+		Unsupported,
 	}
 
 	const STAKING_ERROR: i32 = 8100;
 
+	/// The version of `StakingRpcApi` which added `reward_history`.
+	const REWARD_HISTORY_VERSION: u32 = 3;
+	/// The version of `StakingRpcApi` which added `staking_events`.
+	const STAKING_EVENTS_VERSION: u32 = 4;
+	/// The version of `StakingRpcApi` which added `validators` and `nominators`.
+	const PAGES_VERSION: u32 = 5;
+
 	impl From<Error> for ErrorObjectOwned {
 		fn from(error: Error) -> Self {
 			match error {
@@ -206,154 +297,398 @@ pub mod staking {
 				Error::NoStorage => {
 					ErrorObject::owned(STAKING_ERROR + 3, error.to_string(), None::<()>)
 				},
+				Error::InvalidPage => {
+					ErrorObject::owned(STAKING_ERROR + 4, error.to_string(), None::<()>)
+				},
+				Error::Unsupported => {
+					ErrorObject::owned(STAKING_ERROR + 5, error.to_string(), None::<()>)
+				},
 			}
 		}
 	}
 
+	fn raw_destination(destination: RewardDestination<AccountId>) -> RawRewardDestination {
+		match destination {
+			RewardDestination::Stash => RawRewardDestination::Stash,
+			RewardDestination::Staked => RawRewardDestination::Staked,
+			RewardDestination::Controller => RawRewardDestination::Controller,
+			RewardDestination::Account(acc) => RawRewardDestination::Account(acc.to_string()),
+			RewardDestination::AutoCompound => RawRewardDestination::AutoCompound,
+			RewardDestination::None => RawRewardDestination::None,
+		}
+	}
+
+	fn raw_nominator_info(nominator_info: NominatorInfo) -> RawNominatorInfo {
+		RawNominatorInfo {
+			stash_account: "0x".to_string() + &hex::encode(&nominator_info.stash_account.encode()),
+			target_validators: nominator_info
+				.target_validators
+				.into_iter()
+				.map(|acc| "0x".to_string() + &hex::encode(&acc.encode()))
+				.collect(),
+			total_staking: nominator_info.total_staking.to_string(),
+			active_staking: nominator_info.active_staking.to_string(),
+			rewrds_destination: raw_destination(nominator_info.rewrds_destination),
+			auto_compound: nominator_info.auto_compound.map(|policy| RawCompoundPolicy {
+				threshold: policy.threshold.to_string(),
+				stake: (policy.stake.deconstruct() / 10000000).to_string(),
+			}),
+		}
+	}
+
+	fn raw_validator_info(validator_info: ValidatorInfo) -> RawValidatorInfo {
+		RawValidatorInfo {
+			stash_account: "0x".to_string() + &hex::encode(&validator_info.stash_account.encode()),
+			state: validator_info.is_active,
+			total_staking: validator_info.total_staking.to_string(),
+			owner_staking: validator_info.owner_staking.to_string(),
+			nominators: validator_info.nominators.to_string(),
+			commission: (validator_info.commission.deconstruct() / 10000000).to_string(),
+			can_nominated: validator_info.can_nominated,
+		}
+	}
+
+	fn raw_page<T, R>(
+		page: Result<StakingPage<T>, PageError>,
+		f: impl Fn(T) -> R,
+	) -> Result<RawPage<R>, Error> {
+		let page = page.map_err(|_| Error::InvalidPage)?;
+		Ok(RawPage {
+			items: page.items.into_iter().map(f).collect(),
+			next_page: page.next.map(|acc| "0x".to_string() + &hex::encode(&acc.encode())),
+		})
+	}
+
+	/// Fail with `Error::Unsupported` if the runtime at `at` is older than `version` of
+	/// `StakingRpcApi`.
+	fn ensure_version<C, B>(client: &C, at: B::Hash, version: u32) -> Result<(), Error>
+	where
+		C: ProvideRuntimeApi<B>,
+		C::Api: StakingRpcApi<B>,
+		B: BlockT,
+	{
+		let at_version = client
+			.runtime_api()
+			.api_version::<dyn StakingRpcApi<B>>(at)
+			.map_err(Error::ApiCallErr)?;
+		match at_version {
+			Some(at_version) if at_version >= version => Ok(()),
+			_ => Err(Error::Unsupported),
+		}
+	}
+
+	/// Parse an optional account parameter.
+	fn parse_account(account: Option<String>) -> Result<Option<AccountId>, Error> {
+		account
+			.map(|acc| AccountId::from_str(&acc).map_err(|_| Error::InvalidAccount))
+			.transpose()
+	}
+
 	#[rpc(client, server)]
-	pub trait StakingApi {
+	pub trait StakingApi<BlockHash> {
 		#[method(name = "staking_nominatorInfo")]
-		fn nominator_info(&self, account_id: Vec<String>) -> Result<Vec<RawNominatorInfo>, Error>;
+		fn nominator_info(
+			&self,
+			account_id: Vec<String>,
+			at: Option<BlockHash>,
+		) -> Result<Vec<RawBatchItem<RawNominatorInfo>>, Error>;
 		#[method(name = "staking_validatorInfo")]
-		fn validator_info(&self, account_id: Vec<String>) -> Result<Vec<RawValidatorInfo>, Error>;
+		fn validator_info(
+			&self,
+			account_id: Vec<String>,
+			at: Option<BlockHash>,
+		) -> Result<Vec<RawBatchItem<RawValidatorInfo>>, Error>;
 		#[method(name = "staking_getValidatorRewards")]
 		fn get_validator_rewards(
 			&self,
 			account_id: String,
 			era_index: u32,
+			at: Option<BlockHash>,
 		) -> Result<String, Error>;
 		#[method(name = "staking_getNominatorRewards")]
 		fn get_nominator_rewards(
 			&self,
 			account_id: String,
 			era_index: u32,
+			at: Option<BlockHash>,
 		) -> Result<String, Error>;
 		#[method(name = "staking_getAllValidatorsCanNominate")]
-		fn all_validators_can_nominate(&self) -> Result<Vec<String>, Error>;
+		fn all_validators_can_nominate(&self, at: Option<BlockHash>) -> Result<Vec<String>, Error>;
+		/// The reward totals of `account_id` and what it earned and was paid in each era from
+		/// `from_era` to `to_era`.
+		///
+		/// Only the last `HistoryDepth` eras are retained on chain: 168 on mainnet, 336 on testnet
+		/// and 84 on dev. Older eras are left out of `eras`, so exports that need the full history
+		/// must be taken at least once per window. The totals cover every era.
+		#[method(name = "staking_rewardHistory")]
+		fn reward_history(
+			&self,
+			account_id: String,
+			from_era: u32,
+			to_era: u32,
+			at: Option<BlockHash>,
+		) -> Result<RawRewardHistory, Error>;
+		#[method(name = "staking_validators")]
+		fn validators(
+			&self,
+			page: Option<String>,
+			page_size: u32,
+			sort_by: Option<RawValidatorOrder>,
+			at: Option<BlockHash>,
+		) -> Result<RawPage<RawValidatorInfo>, Error>;
+		#[method(name = "staking_nominators")]
+		fn nominators(
+			&self,
+			validator: Option<String>,
+			page: Option<String>,
+			page_size: u32,
+			at: Option<BlockHash>,
+		) -> Result<RawPage<RawNominatorInfo>, Error>;
+		/// Era payouts and elections, as they are finalized.
+		///
+		/// The subscriptions follow finalized blocks rather than imported ones, so an event is
+		/// delivered once its block is finalized, a few blocks after it is imported, and never
+		/// for a block which is later reorganized away. When several blocks are finalized at
+		/// once, the events of all of them are delivered in block order. Blocks finalized before
+		/// subscribing are not replayed.
+		#[subscription(
+			name = "staking_subscribeEraChanges" => "staking_eraChange",
+			unsubscribe = "staking_unsubscribeEraChanges",
+			item = RawBlockEvent<RawEraChange>,
+		)]
+		fn subscribe_era_changes(&self);
+		/// The era rewards of `account_id`, as they are finalized.
+		#[subscription(
+			name = "staking_subscribeRewards" => "staking_reward",
+			unsubscribe = "staking_unsubscribeRewards",
+			item = RawBlockEvent<RawReward>,
+		)]
+		fn subscribe_rewards(&self, account_id: String);
+		/// Slashes of any staker, as they are finalized.
+		#[subscription(
+			name = "staking_subscribeSlashes" => "staking_slash",
+			unsubscribe = "staking_unsubscribeSlashes",
+			item = RawBlockEvent<RawSlash>,
+		)]
+		fn subscribe_slashes(&self);
 	}
 
 	pub struct StakingClient<C, B> {
 		client: Arc<C>,
+		executor: SubscriptionTaskExecutor,
 		_marker: std::marker::PhantomData<B>,
 	}
 
 	impl<C, B> StakingClient<C, B> {
-		pub fn new(client: Arc<C>) -> Self {
-			StakingClient { client, _marker: Default::default() }
+		pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+			StakingClient { client, executor, _marker: Default::default() }
 		}
 	}
 
-	impl<C, B> StakingApiServer for StakingClient<C, B>
+	impl<C, B> StakingClient<C, B>
 	where
-		C: ProvideRuntimeApi<B>,
-		C: HeaderBackend<B> + 'static,
+		C: HeaderBackend<B>,
+		B: BlockT,
+	{
+		/// The block to query: `at` if given, the best block otherwise.
+		fn block_hash(&self, at: Option<B::Hash>) -> B::Hash {
+			at.unwrap_or_else(|| self.client.info().best_hash)
+		}
+	}
+
+	impl<C, B> StakingClient<C, B>
+	where
+		C: ProvideRuntimeApi<B> + BlockchainEvents<B> + Send + Sync + 'static,
 		C::Api: StakingRpcApi<B>,
-		B: sp_runtime::traits::Block,
+		B: BlockT,
 	{
-		fn nominator_info(&self, accounts: Vec<String>) -> Result<Vec<RawNominatorInfo>, Error> {
-			let api = self.client.runtime_api();
+		/// The staking events of every newly finalized block, mapped with `f`.
+		///
+		/// Blocks of a runtime without `staking_events` are skipped. The stream ends at the first
+		/// block whose events can not be read, rather than silently missing them.
+		fn events_stream<T, F>(&self, f: F) -> impl Stream<Item = RawBlockEvent<T>> + Send
+		where
+			T: Send + 'static,
+			F: Fn(StakingEvent) -> Option<T> + Send + 'static,
+		{
+			let client = self.client.clone();
+			self.client
+				.finality_notification_stream()
+				.map(move |notification| {
+					// `tree_route` holds the blocks finalized along with the notified one.
+					let mut events = Vec::new();
+					for hash in notification.tree_route.iter().chain([&notification.hash]) {
+						let api = client.runtime_api();
+						let version = api.api_version::<dyn StakingRpcApi<B>>(*hash)?;
+						if version.map_or(true, |v| v < STAKING_EVENTS_VERSION) {
+							continue;
+						}
+						let block_hash = "0x".to_string() + &hex::encode(hash.as_ref());
+						events.extend(
+							api.staking_events(*hash)?.into_iter().filter_map(&f).map(|event| {
+								RawBlockEvent { block_hash: block_hash.clone(), event }
+							}),
+						);
+					}
+					Ok::<_, ApiError>(events)
+				})
+				.take_while(|events| {
+					if let Err(e) = events {
+						log::warn!("ending the staking event subscription: {e}");
+					}
+					futures::future::ready(events.is_ok())
+				})
+				.flat_map(|events| futures::stream::iter(events.unwrap_or_default()))
+		}
+
+		/// Reject `pending` unless the best block supports the event subscriptions.
+		fn accept_events(&self, pending: PendingSubscriptionSink) -> Option<PendingSubscriptionSink>
+		where
+			C: HeaderBackend<B>,
+		{
 			let best = self.client.info().best_hash;
+			let error = match ensure_version(&*self.client, best, STAKING_EVENTS_VERSION) {
+				Ok(()) => return Some(pending),
+				Err(error) => error,
+			};
+			self.executor.spawn(
+				"staking-rpc-subscription",
+				Some("rpc"),
+				pending.reject(error).boxed(),
+			);
+			None
+		}
+
+		fn spawn_subscription<T>(
+			&self,
+			pending: PendingSubscriptionSink,
+			stream: impl Stream<Item = T> + Send + 'static,
+		) where
+			T: Serialize + Send + 'static,
+		{
+			self.executor.spawn(
+				"staking-rpc-subscription",
+				Some("rpc"),
+				pipe_from_stream(pending, stream.boxed()).boxed(),
+			);
+		}
+	}
+
+	impl<C, B> StakingApiServer<B::Hash> for StakingClient<C, B>
+	where
+		C: ProvideRuntimeApi<B> + BlockchainEvents<B>,
+		C: HeaderBackend<B> + Send + Sync + 'static,
+		C::Api: StakingRpcApi<B>,
+		B: BlockT,
+	{
+		fn nominator_info(
+			&self,
+			accounts: Vec<String>,
+			at: Option<B::Hash>,
+		) -> Result<Vec<RawBatchItem<RawNominatorInfo>>, Error> {
+			let api = self.client.runtime_api();
+			let at = self.block_hash(at);
+			// Blocks before version 2 return `NominatorInfo` without the compounding policy.
+			let version = api
+				.api_version::<dyn StakingRpcApi<B>>(at)
+				.map_err(Error::ApiCallErr)?
+				.unwrap_or(1);
 			let mut infos = Vec::new();
 			for account in accounts {
-				match AccountId::from_str(&account) {
-					Ok(account_id) => match api.nominator_info(best, &account_id) {
-						Ok(Some(nominator_info)) => {
-							let raw_destination = match nominator_info.rewrds_destination {
-								RewardDestination::Stash => RawRewardDestination::Stash,
-								RewardDestination::Staked => RawRewardDestination::Staked,
-								RewardDestination::Controller => RawRewardDestination::Controller,
-								RewardDestination::Account(acc) => {
-									RawRewardDestination::Account(acc.to_string())
-								},
-								_ => RawRewardDestination::None,
-							};
-							let raw = RawNominatorInfo {
-								stash_account: "0x".to_string()
-									+ &hex::encode(&nominator_info.stash_account.encode()),
-								target_validators: nominator_info
-									.target_validators
-									.into_iter()
-									.map(|acc| "0x".to_string() + &hex::encode(&acc.encode()))
-									.collect(),
-								total_staking: nominator_info.total_staking.to_string(),
-								active_staking: nominator_info.active_staking.to_string(),
-								rewrds_destination: raw_destination,
-							};
-							infos.push(raw);
-						},
-						Ok(None) => return Err(Error::NoStorage),
-						Err(e) => return Err(Error::ApiCallErr(e)),
+				let account_id = match AccountId::from_str(&account) {
+					Ok(account_id) => account_id,
+					Err(_) => {
+						infos.push(RawBatchItem::new(account, Err(Error::InvalidAccount)));
+						continue;
 					},
-					Err(_) => return Err(Error::InvalidAccount),
-				}
+				};
+				let info = if version < 2 {
+					#[allow(deprecated)]
+					api.nominator_info_before_version_2(at, &account_id)
+						.map(|info| info.map(Into::into))
+				} else {
+					api.nominator_info(at, &account_id)
+				};
+				let result = match info {
+					Ok(Some(nominator_info)) => Ok(raw_nominator_info(nominator_info)),
+					Ok(None) => Err(Error::NoStorage),
+					Err(e) => return Err(Error::ApiCallErr(e)),
+				};
+				infos.push(RawBatchItem::new(account, result));
 			}
 			Ok(infos)
 		}
 
-		fn validator_info(&self, accounts: Vec<String>) -> Result<Vec<RawValidatorInfo>, Error> {
+		fn validator_info(
+			&self,
+			accounts: Vec<String>,
+			at: Option<B::Hash>,
+		) -> Result<Vec<RawBatchItem<RawValidatorInfo>>, Error> {
 			let api = self.client.runtime_api();
-			let best = self.client.info().best_hash;
+			let at = self.block_hash(at);
 			let mut infos = Vec::new();
 			for account in accounts {
-				match AccountId::from_str(&account) {
-					Ok(account_id) => match api.validator_info(best, &account_id) {
-						Ok(Some(validator_info)) => {
-							let raw = RawValidatorInfo {
-								stash_account: "0x".to_string()
-									+ &hex::encode(&validator_info.stash_account.encode()),
-								state: validator_info.is_active,
-								total_staking: validator_info.total_staking.to_string(),
-								owner_staking: validator_info.owner_staking.to_string(),
-								nominators: validator_info.nominators.to_string(),
-								commission: (validator_info.commission.deconstruct() / 10000000)
-									.to_string(),
-								can_nominated: validator_info.can_nominated,
-							};
-							infos.push(raw);
-						},
-						Ok(None) => return Err(Error::NoStorage),
-						Err(e) => return Err(Error::ApiCallErr(e)),
+				let account_id = match AccountId::from_str(&account) {
+					Ok(account_id) => account_id,
+					Err(_) => {
+						infos.push(RawBatchItem::new(account, Err(Error::InvalidAccount)));
+						continue;
 					},
-					Err(_) => return Err(Error::InvalidAccount),
-				}
+				};
+				let result = match api.validator_info(at, &account_id) {
+					Ok(Some(validator_info)) => Ok(raw_validator_info(validator_info)),
+					Ok(None) => Err(Error::NoStorage),
+					Err(e) => return Err(Error::ApiCallErr(e)),
+				};
+				infos.push(RawBatchItem::new(account, result));
 			}
 			Ok(infos)
 		}
 
-		fn get_validator_rewards(&self, account: String, era_index: u32) -> Result<String, Error> {
+		fn get_validator_rewards(
+			&self,
+			account: String,
+			era_index: u32,
+			at: Option<B::Hash>,
+		) -> Result<String, Error> {
 			let api = self.client.runtime_api();
-			let best = self.client.info().best_hash;
+			let at = self.block_hash(at);
 			let account_id = match AccountId::from_str(&account) {
 				Ok(acc) => acc,
 				Err(_) => return Err(Error::InvalidAccount),
 			};
 
-			match api.get_validator_rewards(best, &account_id, era_index) {
+			match api.get_validator_rewards(at, &account_id, era_index) {
 				Ok(Some(rewards)) => Ok(rewards.to_string()),
 				Ok(None) => return Err(Error::NoStorage),
 				Err(e) => return Err(Error::ApiCallErr(e)),
 			}
 		}
 
-		fn get_nominator_rewards(&self, account: String, era_index: u32) -> Result<String, Error> {
+		fn get_nominator_rewards(
+			&self,
+			account: String,
+			era_index: u32,
+			at: Option<B::Hash>,
+		) -> Result<String, Error> {
 			let api = self.client.runtime_api();
-			let best = self.client.info().best_hash;
+			let at = self.block_hash(at);
 			let account_id = match AccountId::from_str(&account) {
 				Ok(acc) => acc,
 				Err(_) => return Err(Error::InvalidAccount),
 			};
 
-			match api.get_nominator_rewards(best, &account_id, era_index) {
+			match api.get_nominator_rewards(at, &account_id, era_index) {
 				Ok(Some(rewards)) => Ok(rewards.to_string()),
 				Ok(None) => return Err(Error::NoStorage),
 				Err(e) => return Err(Error::ApiCallErr(e)),
 			}
 		}
 
-		fn all_validators_can_nominate(&self) -> Result<Vec<String>, Error> {
+		fn all_validators_can_nominate(&self, at: Option<B::Hash>) -> Result<Vec<String>, Error> {
 			let api = self.client.runtime_api();
-			let best = self.client.info().best_hash;
-			match api.all_validators_can_nominate(best) {
+			let at = self.block_hash(at);
+			match api.all_validators_can_nominate(at) {
 				Ok(accounts) => Ok(accounts
 					.iter()
 					.map(|acc| "0x".to_string() + &hex::encode(&acc.encode()))
@@ -361,5 +696,467 @@ pub mod staking {
 				Err(e) => return Err(Error::ApiCallErr(e)),
 			}
 		}
+
+		fn reward_history(
+			&self,
+			account: String,
+			from_era: u32,
+			to_era: u32,
+			at: Option<B::Hash>,
+		) -> Result<RawRewardHistory, Error> {
+			let api = self.client.runtime_api();
+			let at = self.block_hash(at);
+			let account_id = match AccountId::from_str(&account) {
+				Ok(acc) => acc,
+				Err(_) => return Err(Error::InvalidAccount),
+			};
+
+			ensure_version(&*self.client, at, REWARD_HISTORY_VERSION)?;
+			match api.reward_history(at, &account_id, from_era, to_era) {
+				Ok(rewards) => Ok(RawRewardHistory {
+					total_rewards: rewards.total_rewards.to_string(),
+					paid_rewards: rewards.paid_rewards.to_string(),
+					eras: rewards
+						.eras
+						.into_iter()
+						.map(|reward| RawEraReward {
+							era: reward.era,
+							earned: reward.earned.to_string(),
+							commission: reward.commission.to_string(),
+							staking: reward.staking.to_string(),
+							paid: reward.paid.to_string(),
+							destination: reward.destination.map(raw_destination),
+						})
+						.collect(),
+				}),
+				Err(e) => return Err(Error::ApiCallErr(e)),
+			}
+		}
+
+		fn validators(
+			&self,
+			page: Option<String>,
+			page_size: u32,
+			sort_by: Option<RawValidatorOrder>,
+			at: Option<B::Hash>,
+		) -> Result<RawPage<RawValidatorInfo>, Error> {
+			let api = self.client.runtime_api();
+			let at = self.block_hash(at);
+			let start = parse_account(page)?;
+			let order = match sort_by.unwrap_or(RawValidatorOrder::Stake) {
+				RawValidatorOrder::Stake => ValidatorOrder::Stake,
+				RawValidatorOrder::Storage => ValidatorOrder::Storage,
+			};
+
+			ensure_version(&*self.client, at, PAGES_VERSION)?;
+			match api.validators(at, start, page_size, order) {
+				Ok(page) => raw_page(page, raw_validator_info),
+				Err(e) => Err(Error::ApiCallErr(e)),
+			}
+		}
+
+		fn nominators(
+			&self,
+			validator: Option<String>,
+			page: Option<String>,
+			page_size: u32,
+			at: Option<B::Hash>,
+		) -> Result<RawPage<RawNominatorInfo>, Error> {
+			let api = self.client.runtime_api();
+			let at = self.block_hash(at);
+			let validator = parse_account(validator)?;
+			let start = parse_account(page)?;
+
+			ensure_version(&*self.client, at, PAGES_VERSION)?;
+			match api.nominators(at, validator, start, page_size) {
+				Ok(page) => raw_page(page, raw_nominator_info),
+				Err(e) => Err(Error::ApiCallErr(e)),
+			}
+		}
+
+		fn subscribe_era_changes(&self, pending: PendingSubscriptionSink) {
+			let Some(pending) = self.accept_events(pending) else { return };
+			let stream = self.events_stream(|event| match event {
+				StakingEvent::EraPayout { era, validator_payout, remainder } => {
+					Some(RawEraChange::EraPayout {
+						era,
+						validator_payout: validator_payout.to_string(),
+						remainder: remainder.to_string(),
+					})
+				},
+				StakingEvent::StakersElected => Some(RawEraChange::StakersElected),
+				_ => None,
+			});
+			self.spawn_subscription(pending, stream);
+		}
+
+		fn subscribe_rewards(&self, pending: PendingSubscriptionSink, account: String) {
+			let Some(pending) = self.accept_events(pending) else { return };
+			let account_id = match AccountId::from_str(&account) {
+				Ok(acc) => acc,
+				Err(_) => {
+					self.executor.spawn(
+						"staking-rpc-subscription",
+						Some("rpc"),
+						pending.reject(Error::InvalidAccount).boxed(),
+					);
+					return;
+				},
+			};
+			let stream = self.events_stream(move |event| match event {
+				StakingEvent::EraReward { era, validator, account: rewarded, commission, staking }
+					if rewarded == account_id =>
+				{
+					Some(RawReward {
+						era,
+						validator: "0x".to_string() + &hex::encode(&validator.encode()),
+						account: "0x".to_string() + &hex::encode(&rewarded.encode()),
+						commission: commission.to_string(),
+						staking: staking.to_string(),
+					})
+				},
+				_ => None,
+			});
+			self.spawn_subscription(pending, stream);
+		}
+
+		fn subscribe_slashes(&self, pending: PendingSubscriptionSink) {
+			let Some(pending) = self.accept_events(pending) else { return };
+			let stream = self.events_stream(|event| match event {
+				StakingEvent::Slashed { staker, amount } => Some(RawSlash {
+					staker: "0x".to_string() + &hex::encode(&staker.encode()),
+					amount: amount.to_string(),
+				}),
+				_ => None,
+			});
+			self.spawn_subscription(pending, stream);
+		}
+	}
+}
+
+pub mod asset_currency {
+	use bp_core::{AccountId, Balance};
+	use codec::Encode;
+	use jsonrpsee::{
+		proc_macros::rpc,
+		types::{ErrorObject, ErrorObjectOwned},
+	};
+	use pallet_asset_currency_runtime_api::{AssetCurrencyApi, BalanceLock, Reasons};
+	use sp_api::{ApiError, ProvideRuntimeApi};
+	use sp_blockchain::HeaderBackend;
+	use sp_runtime::traits::Block as BlockT;
+	use std::str::FromStr;
+	use std::sync::Arc;
+
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawBalanceLock {
+		pub id: String,
+		pub amount: String,
+		pub reasons: String,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawAccountData {
+		pub free: String,
+		pub reserved: String,
+		pub frozen: String,
+		pub locks: Vec<RawBalanceLock>,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawIssuance {
+		pub total_issuance: String,
+		pub inactive_issuance: String,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawWhitelist {
+		pub admin: Option<String>,
+		pub accounts: Vec<String>,
+	}
+
+	#[derive(Debug, thiserror::Error)]
+	/// Top-level error type for the RPC handler
+	pub enum Error {
+		#[error("parse account id failed")]
+		InvalidAccount,
+		#[error("call api error")]
+		ApiCallErr(ApiError),
+	}
+
+	const ASSET_CURRENCY_ERROR: i32 = 8200;
+
+	impl From<Error> for ErrorObjectOwned {
+		fn from(error: Error) -> Self {
+			match error {
+				Error::InvalidAccount => {
+					ErrorObject::owned(ASSET_CURRENCY_ERROR + 1, error.to_string(), None::<()>)
+				},
+				Error::ApiCallErr(_) => {
+					ErrorObject::owned(ASSET_CURRENCY_ERROR + 2, error.to_string(), None::<()>)
+				},
+			}
+		}
+	}
+
+	fn raw_account(account: &AccountId) -> String {
+		"0x".to_string() + &hex::encode(&account.encode())
+	}
+
+	fn raw_lock(lock: BalanceLock<Balance>) -> RawBalanceLock {
+		RawBalanceLock {
+			id: "0x".to_string() + &hex::encode(lock.id),
+			amount: lock.amount.to_string(),
+			reasons: match lock.reasons {
+				Reasons::Fee => "fee",
+				Reasons::Misc => "misc",
+				Reasons::All => "all",
+			}
+			.to_string(),
+		}
+	}
+
+	#[rpc(client, server)]
+	pub trait AssetCurrencyRpcApi<BlockHash> {
+		#[method(name = "assetCurrency_account")]
+		fn account(&self, account_id: String, at: Option<BlockHash>) -> Result<RawAccountData, Error>;
+		#[method(name = "assetCurrency_issuance")]
+		fn issuance(&self, at: Option<BlockHash>) -> Result<RawIssuance, Error>;
+		#[method(name = "assetCurrency_controllers")]
+		fn controllers(&self, at: Option<BlockHash>) -> Result<Vec<String>, Error>;
+		#[method(name = "assetCurrency_whitelist")]
+		fn whitelist(&self, at: Option<BlockHash>) -> Result<RawWhitelist, Error>;
+	}
+
+	pub struct AssetCurrencyClient<C, B> {
+		client: Arc<C>,
+		_marker: std::marker::PhantomData<B>,
+	}
+
+	impl<C, B> AssetCurrencyClient<C, B> {
+		pub fn new(client: Arc<C>) -> Self {
+			AssetCurrencyClient { client, _marker: Default::default() }
+		}
+	}
+
+	impl<C, B> AssetCurrencyRpcApiServer<B::Hash> for AssetCurrencyClient<C, B>
+	where
+		C: ProvideRuntimeApi<B>,
+		C: HeaderBackend<B> + Send + Sync + 'static,
+		C::Api: AssetCurrencyApi<B, AccountId, Balance>,
+		B: BlockT,
+	{
+		fn account(
+			&self,
+			account: String,
+			at: Option<B::Hash>,
+		) -> Result<RawAccountData, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let account_id = AccountId::from_str(&account).map_err(|_| Error::InvalidAccount)?;
+
+			let data = api.account(at, account_id).map_err(Error::ApiCallErr)?;
+			let locks = api.locks(at, account_id).map_err(Error::ApiCallErr)?;
+			Ok(RawAccountData {
+				free: data.free.to_string(),
+				reserved: data.reserved.to_string(),
+				frozen: data.frozen.to_string(),
+				locks: locks.into_iter().map(raw_lock).collect(),
+			})
+		}
+
+		fn issuance(&self, at: Option<B::Hash>) -> Result<RawIssuance, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let (total, inactive) = api.issuance(at).map_err(Error::ApiCallErr)?;
+			Ok(RawIssuance {
+				total_issuance: total.to_string(),
+				inactive_issuance: inactive.to_string(),
+			})
+		}
+
+		fn controllers(&self, at: Option<B::Hash>) -> Result<Vec<String>, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let controllers = api.controllers(at).map_err(Error::ApiCallErr)?;
+			Ok(controllers.iter().map(raw_account).collect())
+		}
+
+		fn whitelist(&self, at: Option<B::Hash>) -> Result<RawWhitelist, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let (admin, accounts) = api.whitelist(at).map_err(Error::ApiCallErr)?;
+			Ok(RawWhitelist {
+				admin: admin.as_ref().map(raw_account),
+				accounts: accounts.iter().map(raw_account).collect(),
+			})
+		}
+	}
+}
+
+pub mod provider {
+	use bp_core::{AccountId, Balance, BlockNumber};
+	use codec::Encode;
+	use jsonrpsee::{
+		proc_macros::rpc,
+		types::{ErrorObject, ErrorObjectOwned},
+	};
+	use pallet_provider_runtime_api::{DeviceId, DeviceInfo, ProviderApi, ProviderId, ProviderInfo};
+	use sp_api::{ApiError, ProvideRuntimeApi};
+	use sp_blockchain::HeaderBackend;
+	use sp_runtime::traits::Block as BlockT;
+	use std::str::FromStr;
+	use std::sync::Arc;
+
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawProvider {
+		pub pid: String,
+		pub owner: String,
+		pub cap_pledge: String,
+		pub total_pledge: String,
+		pub devices_num: String,
+		pub total_punishment: String,
+		pub total_rewards: String,
+		pub unpaid_rewards: String,
+	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	pub struct RawDevice {
+		pub id: String,
+		pub pledge: String,
+		pub registered_at: u32,
+	}
+
+	#[derive(Debug, thiserror::Error)]
+	/// Top-level error type for the RPC handler
+	pub enum Error {
+		#[error("parse account id failed")]
+		InvalidAccount,
+		#[error("call api error")]
+		ApiCallErr(ApiError),
+	}
+
+	const PROVIDER_ERROR: i32 = 8400;
+
+	impl From<Error> for ErrorObjectOwned {
+		fn from(error: Error) -> Self {
+			match error {
+				Error::InvalidAccount => {
+					ErrorObject::owned(PROVIDER_ERROR + 1, error.to_string(), None::<()>)
+				},
+				Error::ApiCallErr(_) => {
+					ErrorObject::owned(PROVIDER_ERROR + 2, error.to_string(), None::<()>)
+				},
+			}
+		}
+	}
+
+	fn raw_provider(pid: ProviderId, provider: ProviderInfo<AccountId, Balance>) -> RawProvider {
+		RawProvider {
+			pid: pid.to_string(),
+			owner: "0x".to_string() + &hex::encode(&provider.owner.encode()),
+			cap_pledge: provider.cap_pledge.to_string(),
+			total_pledge: provider.total_pledge.to_string(),
+			devices_num: provider.devices_num.to_string(),
+			total_punishment: provider.total_punishment.to_string(),
+			total_rewards: provider.total_rewards.to_string(),
+			unpaid_rewards: provider.unpaid_rewards.to_string(),
+		}
+	}
+
+	fn raw_device((id, device): (DeviceId, DeviceInfo<Balance, BlockNumber>)) -> RawDevice {
+		RawDevice {
+			id: "0x".to_string() + &hex::encode(id),
+			pledge: device.pledge.to_string(),
+			registered_at: device.registered_at,
+		}
+	}
+
+	#[rpc(client, server)]
+	pub trait ProviderRpcApi<BlockHash> {
+		#[method(name = "provider_provider")]
+		fn provider(&self, pid: u64, at: Option<BlockHash>) -> Result<Option<RawProvider>, Error>;
+		#[method(name = "provider_providerOf")]
+		fn provider_of(
+			&self,
+			owner: String,
+			at: Option<BlockHash>,
+		) -> Result<Option<RawProvider>, Error>;
+		#[method(name = "provider_providers")]
+		fn providers(
+			&self,
+			start: Option<u64>,
+			count: Option<u32>,
+			at: Option<BlockHash>,
+		) -> Result<Vec<RawProvider>, Error>;
+		#[method(name = "provider_devices")]
+		fn devices(&self, pid: u64, at: Option<BlockHash>) -> Result<Vec<RawDevice>, Error>;
+	}
+
+	pub struct ProviderClient<C, B> {
+		client: Arc<C>,
+		_marker: std::marker::PhantomData<B>,
+	}
+
+	impl<C, B> ProviderClient<C, B> {
+		pub fn new(client: Arc<C>) -> Self {
+			ProviderClient { client, _marker: Default::default() }
+		}
+	}
+
+	impl<C, B> ProviderRpcApiServer<B::Hash> for ProviderClient<C, B>
+	where
+		C: ProvideRuntimeApi<B>,
+		C: HeaderBackend<B> + Send + Sync + 'static,
+		C::Api: ProviderApi<B, AccountId, Balance, BlockNumber>,
+		B: BlockT,
+	{
+		fn provider(&self, pid: u64, at: Option<B::Hash>) -> Result<Option<RawProvider>, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let provider = api.provider(at, pid).map_err(Error::ApiCallErr)?;
+			Ok(provider.map(|provider| raw_provider(pid, provider)))
+		}
+
+		fn provider_of(
+			&self,
+			owner: String,
+			at: Option<B::Hash>,
+		) -> Result<Option<RawProvider>, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let owner = AccountId::from_str(&owner).map_err(|_| Error::InvalidAccount)?;
+
+			let Some(pid) = api.provider_of(at, owner).map_err(Error::ApiCallErr)? else {
+				return Ok(None);
+			};
+			let provider = api.provider(at, pid).map_err(Error::ApiCallErr)?;
+			Ok(provider.map(|provider| raw_provider(pid, provider)))
+		}
+
+		fn providers(
+			&self,
+			start: Option<u64>,
+			count: Option<u32>,
+			at: Option<B::Hash>,
+		) -> Result<Vec<RawProvider>, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let count = count.unwrap_or(pallet_provider_runtime_api::MAX_PAGE_SIZE);
+			let providers =
+				api.providers(at, start.unwrap_or_default(), count).map_err(Error::ApiCallErr)?;
+			Ok(providers.into_iter().map(|(pid, provider)| raw_provider(pid, provider)).collect())
+		}
+
+		fn devices(&self, pid: u64, at: Option<B::Hash>) -> Result<Vec<RawDevice>, Error> {
+			let api = self.client.runtime_api();
+			let at = at.unwrap_or_else(|| self.client.info().best_hash);
+			let devices = api.devices(at, pid).map_err(Error::ApiCallErr)?;
+			Ok(devices.into_iter().map(raw_device).collect())
+		}
 	}
 }
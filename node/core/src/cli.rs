@@ -1,5 +1,10 @@
+use std::{ffi::OsString, path::PathBuf};
+
 use sc_cli::RunCmd;
-use taker_common_node::cli_opt::{BackendType, EthApi};
+use taker_common_node::{
+	cli_opt::{BackendType, EthApi, Sealing},
+	frontier_db::FrontierDbOp,
+};
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -9,8 +14,15 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub run: RunCmd,
 
+	/// Read options from a TOML file, keyed by their long names. Options given on the command
+	/// line take precedence over the file.
+	#[clap(long, value_name = "PATH")]
+	pub config: Option<PathBuf>,
+
+	/// Seal blocks on demand instead of running BABE and GRANDPA. Dev chain only.
+	/// ex) --sealing=instant, --sealing=manual, --sealing=6000
 	#[clap(long)]
-	pub sealing: bool,
+	pub sealing: Option<Sealing>,
 
 	/// Enable EVM tracing & Txpool module. ex) --ethapi=debug,trace,txpool
 	#[clap(long, value_delimiter = ',')]
@@ -106,4 +118,47 @@ pub enum Subcommand {
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[clap(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Replay a runtime upgrade against a state snapshot.
+	TryRuntime(crate::try_runtime::TryRuntimeCmd),
+
+	/// Maintain the Frontier mapping database.
+	FrontierDb(FrontierDbCmd),
+
+	/// Print the options the node runs with, merged from `--config` and the command line.
+	PrintConfig(PrintConfigCmd),
+}
+
+/// Print the effective node options as a config file.
+#[derive(Debug, clap::Parser)]
+pub struct PrintConfigCmd {
+	/// The node options, as they would be passed to the node.
+	/// ex) print-config --config node.toml --ethapi=debug
+	#[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+	pub args: Vec<OsString>,
+}
+
+/// Export, import, rebuild, verify or migrate the Ethereum block and transaction mappings.
+#[derive(Debug, clap::Parser)]
+pub struct FrontierDbCmd {
+	#[clap(subcommand)]
+	pub op: FrontierDbOp,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for FrontierDbCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
 }
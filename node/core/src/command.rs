@@ -1,6 +1,10 @@
 use crate::cli::{Cli, Subcommand};
 
-use taker_common_node::cli_opt::{BackendType, BackendTypeConfig, RpcConfig};
+use taker_common_node::{
+	cli_opt::{BackendType, BackendTypeConfig, RpcConfig},
+	frontier_db,
+	service::open_frontier_backend,
+};
 
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
 
@@ -82,7 +86,17 @@ impl SubstrateCli for Cli {
 
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
-	let cli = Cli::from_args();
+	let cli = crate::config::from_args()?;
+
+	let backend_type_config = |backend_type| match backend_type {
+		BackendType::KeyValue => BackendTypeConfig::KeyValue,
+		BackendType::Sql => BackendTypeConfig::Sql {
+			pool_size: cli.frontier_sql_backend_pool_size,
+			num_ops_timeout: cli.frontier_sql_backend_num_ops_timeout,
+			thread_count: cli.frontier_sql_backend_thread_count,
+			cache_size: cli.frontier_sql_backend_cache_size,
+		},
+	};
 
 	let rpc_config = RpcConfig {
 		ethapi: cli.ethapi.clone(),
@@ -95,15 +109,7 @@ pub fn run() -> sc_cli::Result<()> {
 		max_past_logs: cli.max_past_logs,
 		logs_request_timeout: cli.logs_request_timeout,
 		tracing_raw_max_memory_usage: cli.tracing_raw_max_memory_usage,
-		frontier_backend_type: match cli.frontier_backend_type {
-			BackendType::KeyValue => BackendTypeConfig::KeyValue,
-			BackendType::Sql => BackendTypeConfig::Sql {
-				pool_size: cli.frontier_sql_backend_pool_size,
-				num_ops_timeout: cli.frontier_sql_backend_num_ops_timeout,
-				thread_count: cli.frontier_sql_backend_thread_count,
-				cache_size: cli.frontier_sql_backend_cache_size,
-			},
-		},
+		frontier_backend_type: backend_type_config(cli.frontier_backend_type),
 	};
 
 	match &cli.subcommand {
@@ -226,6 +232,7 @@ pub fn run() -> sc_cli::Result<()> {
 				})
 			}
 		},
+		Some(Subcommand::PrintConfig(cmd)) => crate::config::print(&cmd.args),
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
@@ -328,13 +335,150 @@ pub fn run() -> sc_cli::Result<()> {
 				}
 			})
 		},
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::TryRuntime(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			let code = if chain_spec.is_testnet() {
+				taker_testnet_runtime::WASM_BINARY
+			} else if chain_spec.is_mainnet() {
+				taker_mainnet_runtime::WASM_BINARY
+			} else {
+				taker_dev_runtime::WASM_BINARY
+			};
+			runner.sync_run(|_| cmd.run(code.ok_or("WASM binary not available")?))
+		},
+		#[cfg(not(feature = "try-runtime"))]
+		Some(Subcommand::TryRuntime(_)) => Err("TryRuntime wasn't enabled when building the node. \
+			You can enable it with `--features try-runtime`."
+			.into()),
+		Some(Subcommand::FrontierDb(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			if chain_spec.is_dev() {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						other: (_, _, frontier_backend, ..),
+						..
+					} = taker_dev_node::service::new_partial(&config, &rpc_config)?;
+					let open_backend = {
+						let client = client.clone();
+						move |to| {
+							let rpc_config = RpcConfig {
+								frontier_backend_type: backend_type_config(to),
+								..rpc_config.clone()
+							};
+							open_frontier_backend(client, &config, &rpc_config)
+						}
+					};
+					Ok((
+						async move {
+							frontier_db::run(&cmd.op, client, frontier_backend, open_backend)
+								.await
+								.map_err(Into::into)
+						},
+						task_manager,
+					))
+				})
+			} else if chain_spec.is_testnet() {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						other: (_, _, frontier_backend, ..),
+						..
+					} = taker_testnet_node::service::new_partial(&config, &rpc_config)?;
+					let open_backend = {
+						let client = client.clone();
+						move |to| {
+							let rpc_config = RpcConfig {
+								frontier_backend_type: backend_type_config(to),
+								..rpc_config.clone()
+							};
+							open_frontier_backend(client, &config, &rpc_config)
+						}
+					};
+					Ok((
+						async move {
+							frontier_db::run(&cmd.op, client, frontier_backend, open_backend)
+								.await
+								.map_err(Into::into)
+						},
+						task_manager,
+					))
+				})
+			} else if chain_spec.is_mainnet() {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						other: (_, _, frontier_backend, ..),
+						..
+					} = taker_mainnet_node::service::new_partial(&config, &rpc_config)?;
+					let open_backend = {
+						let client = client.clone();
+						move |to| {
+							let rpc_config = RpcConfig {
+								frontier_backend_type: backend_type_config(to),
+								..rpc_config.clone()
+							};
+							open_frontier_backend(client, &config, &rpc_config)
+						}
+					};
+					Ok((
+						async move {
+							frontier_db::run(&cmd.op, client, frontier_backend, open_backend)
+								.await
+								.map_err(Into::into)
+						},
+						task_manager,
+					))
+				})
+			} else {
+				runner.async_run(|config| {
+					let PartialComponents {
+						client,
+						task_manager,
+						other: (_, _, frontier_backend, ..),
+						..
+					} = taker_dev_node::service::new_partial(&config, &rpc_config)?;
+					let open_backend = {
+						let client = client.clone();
+						move |to| {
+							let rpc_config = RpcConfig {
+								frontier_backend_type: backend_type_config(to),
+								..rpc_config.clone()
+							};
+							open_frontier_backend(client, &config, &rpc_config)
+						}
+					};
+					Ok((
+						async move {
+							frontier_db::run(&cmd.op, client, frontier_backend, open_backend)
+								.await
+								.map_err(Into::into)
+						},
+						task_manager,
+					))
+				})
+			}
+		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			let chain_spec = &runner.config().chain_spec;
+			let sealing = cli.sealing;
+
+			if sealing.is_some() && (chain_spec.is_testnet() || chain_spec.is_mainnet()) {
+				return Err("`--sealing` is only supported on the dev chain".into());
+			}
 
 			if chain_spec.is_dev() {
 				runner.run_node_until_exit(|config| async move {
-					taker_dev_node::service::new_full(config, rpc_config)
+					taker_dev_node::service::new_full(config, rpc_config, sealing)
 						.map_err(sc_cli::Error::Service)
 				})
 			} else if chain_spec.is_testnet() {
@@ -349,7 +493,7 @@ pub fn run() -> sc_cli::Result<()> {
 				})
 			} else {
 				runner.run_node_until_exit(|config| async move {
-					taker_dev_node::service::new_full(config, rpc_config)
+					taker_dev_node::service::new_full(config, rpc_config, sealing)
 						.map_err(sc_cli::Error::Service)
 				})
 			}
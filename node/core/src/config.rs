@@ -0,0 +1,168 @@
+//! Node options loaded from a `--config` TOML file.
+//!
+//! The file maps the long name of any node option to its value, e.g.
+//!
+//! ```toml
+//! chain = "testnet_local"
+//! base-path = "./base/alice"
+//! validator = true
+//! ethapi = ["debug", "trace", "txpool"]
+//! ethapi-max-permits = 1000
+//! ```
+//!
+//! Options given on the command line take precedence over the file. Subcommands do not read
+//! the file.
+
+use crate::cli::Cli;
+
+use std::{ffi::OsString, path::PathBuf};
+
+use clap::{parser::ValueSource, Arg, ArgAction, ArgMatches, CommandFactory};
+use sc_cli::SubstrateCli;
+use toml::{Table, Value};
+
+/// Parse the command line, with the options it leaves unset read from `--config`.
+pub fn from_args() -> sc_cli::Result<Cli> {
+	let args = merge(std::env::args_os().collect())?;
+	Ok(Cli::from_iter(args))
+}
+
+/// Print the options the node would run with, given `args`, as a config file.
+pub fn print(args: &[OsString]) -> sc_cli::Result<()> {
+	let args = std::iter::once(Cli::executable_name().into()).chain(args.iter().cloned());
+	let args = merge(args.collect())?;
+	let command = Cli::command();
+	let matches = command.clone().try_get_matches_from(args).map_err(|e| e.to_string())?;
+
+	let mut table = Table::new();
+	for arg in command.get_arguments() {
+		let Some(long) = arg.get_long() else { continue };
+		if long == "config" || matches.value_source(arg.get_id().as_str()).is_none() {
+			continue;
+		}
+		if let Some(value) = to_value(arg, &matches) {
+			table.insert(long.into(), value);
+		}
+	}
+	print!("{}", toml::to_string(&table).map_err(|e| e.to_string())?);
+	Ok(())
+}
+
+/// Insert the options of the `--config` file which are not given in `args` into `args`.
+fn merge(mut args: Vec<OsString>) -> Result<Vec<OsString>, String> {
+	let command = Cli::command();
+	// Leave malformed command lines to the actual parse.
+	let Ok(matches) = command.clone().try_get_matches_from(&args) else { return Ok(args) };
+	let Some(path) = matches.get_one::<PathBuf>("config") else { return Ok(args) };
+	// The file holds node options, which subcommands do not read.
+	if let Some((name, _)) = matches.subcommand() {
+		return Err(format!("`--config` is not supported by the `{name}` subcommand"));
+	}
+
+	let table = std::fs::read_to_string(path)
+		.map_err(|e| format!("failed to read {}: {e}", path.display()))?
+		.parse::<Table>()
+		.map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+
+	let mut file_args = Vec::new();
+	for (key, value) in table {
+		let arg = command
+			.get_arguments()
+			.find(|arg| arg.get_long() == Some(key.as_str()) && key != "config")
+			.ok_or_else(|| format!("{}: unknown option `{key}`", path.display()))?;
+		if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+			continue;
+		}
+		file_args.extend(
+			to_args(arg, &key, value).map_err(|e| format!("{}: `{key}` {e}", path.display()))?,
+		);
+	}
+	args.splice(1..1, file_args);
+
+	Ok(args)
+}
+
+/// The command line arguments setting `arg` to `value`.
+fn to_args(arg: &Arg, key: &str, value: Value) -> Result<Vec<OsString>, String> {
+	let scalar = |value: Value| match value {
+		Value::String(value) => Ok(value),
+		Value::Integer(value) => Ok(value.to_string()),
+		Value::Float(value) => Ok(value.to_string()),
+		Value::Boolean(value) => Ok(value.to_string()),
+		_ => Err("must be a string, a number, a boolean or an array of those"),
+	};
+
+	Ok(match value {
+		Value::Boolean(flag) if !arg.get_action().takes_values() =>
+			if flag {
+				vec![format!("--{key}").into()]
+			} else {
+				vec![]
+			},
+		Value::Array(values) => values
+			.into_iter()
+			.map(|value| Ok(format!("--{key}={}", scalar(value)?).into()))
+			.collect::<Result<_, String>>()?,
+		value => vec![format!("--{key}={}", scalar(value)?).into()],
+	})
+}
+
+/// The value of `arg` in `matches`, in the form `to_args` reads it back.
+fn to_value(arg: &Arg, matches: &ArgMatches) -> Option<Value> {
+	let value = |raw: &std::ffi::OsStr| {
+		let raw = raw.to_string_lossy();
+		raw.parse::<i64>().map(Value::Integer).unwrap_or_else(|_| Value::String(raw.into()))
+	};
+
+	let mut raw = matches.get_raw(arg.get_id().as_str())?;
+	Some(match arg.get_action() {
+		ArgAction::SetTrue => Value::Boolean(raw.next()? == "true"),
+		ArgAction::Append => Value::Array(raw.map(value).collect()),
+		_ => value(raw.next()?),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Write `contents` to a config file unique to `test`, and return its path.
+	fn config_file(test: &str, contents: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("taker-config-{test}.toml"));
+		std::fs::write(&path, contents).unwrap();
+		path
+	}
+
+	fn args(args: &[&str]) -> Vec<OsString> {
+		std::iter::once("taker-node").chain(args.iter().copied()).map(Into::into).collect()
+	}
+
+	#[test]
+	fn command_line_takes_precedence_over_the_file() {
+		let path = config_file("precedence", "name = \"file\"\nvalidator = true\n");
+		let path = path.to_str().unwrap();
+
+		let merged = merge(args(&["--config", path, "--name", "cli"])).unwrap();
+		let matches = Cli::command().try_get_matches_from(merged).unwrap();
+		assert_eq!(matches.get_one::<String>("name").map(String::as_str), Some("cli"));
+		assert!(matches.get_flag("validator"));
+
+		let merged = merge(args(&["--config", path])).unwrap();
+		let matches = Cli::command().try_get_matches_from(merged).unwrap();
+		assert_eq!(matches.get_one::<String>("name").map(String::as_str), Some("file"));
+	}
+
+	#[test]
+	fn unknown_options_are_rejected() {
+		let path = config_file("unknown", "no-such-option = 1\n");
+		let error = merge(args(&["--config", path.to_str().unwrap()])).unwrap_err();
+		assert!(error.ends_with("unknown option `no-such-option`"), "{error}");
+	}
+
+	#[test]
+	fn subcommands_reject_the_file() {
+		let path = config_file("subcommand", "validator = true\n");
+		let error = merge(args(&["--config", path.to_str().unwrap(), "purge-chain"])).unwrap_err();
+		assert!(error.contains("purge-chain"), "{error}");
+	}
+}
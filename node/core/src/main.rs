@@ -1,5 +1,7 @@
 mod cli;
 mod command;
+mod config;
+mod try_runtime;
 
 #[cfg(not(target_env = "msvc"))]
 use tikv_jemallocator::Jemalloc;
@@ -0,0 +1,158 @@
+//! Replay a runtime upgrade against a state snapshot, before releasing it.
+//!
+//! Snapshots are created from a live node with `try-runtime create-snapshot`, see
+//! <https://github.com/paritytech/try-runtime-cli>.
+
+use std::path::PathBuf;
+
+/// The checks run around `on_runtime_upgrade`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum UpgradeChecks {
+	/// Run no checks.
+	None,
+	/// Run the `pre_upgrade` and `post_upgrade` hooks, and `try_state` of every pallet.
+	All,
+	/// Run the `pre_upgrade` and `post_upgrade` hooks.
+	PreAndPost,
+	/// Run `try_state` of every pallet.
+	TryState,
+}
+
+/// Run `on_runtime_upgrade` of a new runtime, and its checks, against a state snapshot.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct TryRuntimeCmd {
+	/// The state snapshot to run against.
+	#[arg(long)]
+	pub snapshot: PathBuf,
+
+	/// The wasm runtime to upgrade to. Defaults to the runtime built into this node for the chain.
+	#[arg(long)]
+	pub runtime: Option<PathBuf>,
+
+	/// The checks to run around the upgrade.
+	#[arg(long, value_enum, default_value_t = UpgradeChecks::All)]
+	pub checks: UpgradeChecks,
+
+	/// Do not fail when the upgrade weight exceeds the maximum block weight.
+	#[arg(long)]
+	pub no_weight_check: bool,
+
+	/// Do not fail when the runtime to upgrade to does not bump `spec_version`.
+	#[arg(long)]
+	pub no_spec_version_check: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+}
+
+impl sc_cli::CliConfiguration for TryRuntimeCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+}
+
+#[cfg(feature = "try-runtime")]
+impl TryRuntimeCmd {
+	/// Upgrade the snapshot to the runtime, `builtin` unless `--runtime` is given, and run the
+	/// checks.
+	pub fn run(&self, builtin: &[u8]) -> sc_cli::Result<()> {
+		use codec::{Decode, Encode};
+		use frame_remote_externalities::{Builder, Mode, OfflineConfig, SnapshotConfig};
+		use frame_try_runtime::UpgradeCheckSelect;
+		use sc_executor::WasmExecutor;
+		use sp_core::{
+			storage::well_known_keys,
+			traits::{CallContext, ReadRuntimeVersion},
+		};
+		use sp_state_machine::{backend::BackendRuntimeCode, OverlayedChanges, StateMachine};
+		use sp_version::RuntimeVersion;
+		use sp_weights::Weight;
+		use taker_common_node::{rpc::Block, service::HostFunctions};
+
+		let executor = WasmExecutor::<HostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+
+		let mut ext = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()?
+			.block_on(
+				Builder::<Block>::new()
+					.mode(Mode::Offline(OfflineConfig {
+						state_snapshot: SnapshotConfig::new(&self.snapshot),
+					}))
+					.build(),
+			)
+			.map_err(|e| format!("failed to load snapshot {}: {e}", self.snapshot.display()))?;
+
+		let old_code = ext
+			.execute_with(|| sp_io::storage::get(well_known_keys::CODE))
+			.ok_or("snapshot has no runtime code")?;
+		let new_code = match &self.runtime {
+			Some(path) => std::fs::read(path)?,
+			None => builtin.to_vec(),
+		};
+		let mut runtime_version = |code: &[u8]| -> sc_cli::Result<RuntimeVersion> {
+			let encoded = executor
+				.read_runtime_version(code, &mut ext.ext())
+				.map_err(|e| format!("failed to read runtime version: {e}"))?;
+			Ok(RuntimeVersion::decode(&mut &*encoded)
+				.map_err(|e| format!("failed to decode runtime version: {e}"))?)
+		};
+		let old_version = runtime_version(&old_code)?;
+		let new_version = runtime_version(&new_code)?;
+		log::info!(
+			"upgrading {} from spec_version {} to {} {}",
+			old_version.spec_name,
+			old_version.spec_version,
+			new_version.spec_name,
+			new_version.spec_version,
+		);
+		if old_version.spec_name != new_version.spec_name {
+			return Err(format!(
+				"runtime {} can not upgrade {}",
+				new_version.spec_name, old_version.spec_name
+			)
+			.into());
+		}
+		if new_version.spec_version <= old_version.spec_version && !self.no_spec_version_check {
+			return Err("the runtime does not bump spec_version".into());
+		}
+
+		ext.insert(well_known_keys::CODE.to_vec(), new_code);
+		let backend = ext.as_backend();
+		let runtime_code_backend = BackendRuntimeCode::new(&backend);
+		let runtime_code = runtime_code_backend.runtime_code()?;
+		let checks = match self.checks {
+			UpgradeChecks::None => UpgradeCheckSelect::None,
+			UpgradeChecks::All => UpgradeCheckSelect::All,
+			UpgradeChecks::PreAndPost => UpgradeCheckSelect::PreAndPost,
+			UpgradeChecks::TryState => UpgradeCheckSelect::TryState,
+		};
+
+		let encoded = StateMachine::new(
+			&backend,
+			&mut OverlayedChanges::default(),
+			&executor,
+			"TryRuntime_on_runtime_upgrade",
+			&checks.encode(),
+			&mut Default::default(),
+			&runtime_code,
+			CallContext::Offchain,
+		)
+		.execute()
+		.map_err(|e| format!("on_runtime_upgrade failed: {e}"))?;
+
+		let (weight, max_weight) = <(Weight, Weight)>::decode(&mut &*encoded)
+			.map_err(|e| format!("failed to decode upgrade weight: {e}"))?;
+		log::info!(
+			"on_runtime_upgrade consumed {weight:?} of the maximum block weight {max_weight:?}"
+		);
+		if weight.any_gt(max_weight) && !self.no_weight_check {
+			return Err("the upgrade exceeds the maximum block weight".into());
+		}
+
+		Ok(())
+	}
+}
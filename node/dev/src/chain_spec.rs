@@ -79,7 +79,7 @@ pub fn development_config() -> ChainSpec {
 		.with_name("Taker Devnet")
 		.with_id("Devnet")
 		.with_protocol_id("takerDevnet")
-		.with_chain_type(ChainType::Local)
+		.with_chain_type(ChainType::Development)
 		.with_properties(properties())
 		.with_genesis_config_patch(dev_genesis(
 			// Sudo account
@@ -196,6 +196,7 @@ fn dev_genesis(
 		ethereum: Default::default(),
 		dynamic_fee: Default::default(),
 		base_fee: Default::default(),
+		fee_sharing: Default::default(),
 	};
 
 	serde_json::to_value(&config).expect("Could not build genesis config.")
@@ -5,6 +5,9 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "dev-tools")]
+pub mod dev;
+
 use jsonrpsee::RpcModule;
 use std::sync::Arc;
 
@@ -20,7 +23,7 @@ use taker_common_node::{
 	cli_opt::EthApi as EthApiCmd,
 	rpc::{BabeDeps, DefaultEthConfig, TracingConfig},
 };
-use taker_dev_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use taker_dev_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce};
 
 use sc_client_api::{
 	backend::{Backend, StateBackend, StorageProvider},
@@ -30,7 +33,23 @@ pub use sc_client_api::{AuxStore, BlockOf, BlockchainEvents};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::TransactionPool;
-use taker_common_node::rpc::{staking::StakingApiServer, FullDeps, GrandpaDeps};
+use taker_common_node::rpc::{
+	asset_currency::AssetCurrencyRpcApiServer, provider::ProviderRpcApiServer,
+	staking::StakingApiServer, FullDeps, GrandpaDeps,
+};
+
+use crate::service::ManualTimestamp;
+use sc_consensus_manual_seal::rpc::EngineCommand;
+use sp_core::H256;
+
+/// Dependencies of the RPCs only available when blocks are sealed on demand.
+#[derive(Clone)]
+pub struct SealingDeps {
+	/// Sends commands to the manual seal authorship task.
+	pub command_sink: futures::channel::mpsc::Sender<EngineCommand<H256>>,
+	/// The timestamp of the blocks to seal.
+	pub timestamp: ManualTimestamp,
+}
 
 /// Instantiate all full RPC extensions.
 pub fn create_full<C, P, BE, SC, A, CIDP>(
@@ -41,6 +60,7 @@ pub fn create_full<C, P, BE, SC, A, CIDP>(
 			fc_mapping_sync::EthereumBlockNotification<Block>,
 		>,
 	>,
+	sealing: Option<SealingDeps>,
 ) -> Result<RpcModule<()>, sc_service::Error>
 where
 	BE: Backend<Block> + Send + Sync + 'static,
@@ -61,6 +81,8 @@ where
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: fp_rpc_txpool::TxPoolRuntimeApi<Block>,
 	C::Api: pallet_staking_runtime_api::StakingRpcApi<Block>,
+	C::Api: pallet_asset_currency_runtime_api::AssetCurrencyApi<Block, AccountId, Balance>,
+	C::Api: pallet_provider_runtime_api::ProviderApi<Block, AccountId, Balance, BlockNumber>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 	SC: SelectChain<Block> + 'static,
@@ -76,6 +98,9 @@ where
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+	#[cfg(feature = "dev-tools")]
+	use dev::DevApiServer;
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut io = RpcModule::new(());
@@ -84,7 +109,7 @@ where
 		client,
 		pool,
 		select_chain,
-		chain_spec: _,
+		chain_spec,
 		deny_unsafe,
 		graph,
 		network,
@@ -105,6 +130,8 @@ where
 		sync_service,
 		pending_create_inherent_data_providers,
 	} = deps;
+	#[cfg(not(feature = "dev-tools"))]
+	let _ = chain_spec;
 
 	let BabeDeps { babe_worker_handle, keystore } = babe;
 
@@ -166,8 +193,34 @@ where
 
 	io.merge(Web3::new(&client_version).into_rpc()).ok();
 
+	if let Some(sealing) = sealing {
+		io.merge(ManualSeal::new(sealing.command_sink.clone()).into_rpc()).ok();
+		// Development helpers which bypass sudo, never served on a live chain.
+		#[cfg(feature = "dev-tools")]
+		if chain_spec.chain_type() == sc_service::ChainType::Development {
+			io.merge(dev::Dev::new(Arc::clone(&client), Arc::clone(&pool), sealing).into_rpc())
+				.ok();
+		}
+	}
+
 	// pallet_staking rpc
-	io.merge(taker_common_node::rpc::staking::StakingClient::new(client.clone()).into_rpc())
+	io.merge(
+		taker_common_node::rpc::staking::StakingClient::new(
+			client.clone(),
+			Arc::clone(&subscription_executor),
+		)
+		.into_rpc(),
+	)
+	.ok();
+
+	// pallet_asset_currency rpc
+	io.merge(
+		taker_common_node::rpc::asset_currency::AssetCurrencyClient::new(client.clone()).into_rpc(),
+	)
+	.ok();
+
+	// pallet_provider rpc
+	io.merge(taker_common_node::rpc::provider::ProviderClient::new(client.clone()).into_rpc())
 		.ok();
 
 	io.merge(
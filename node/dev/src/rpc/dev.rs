@@ -0,0 +1,199 @@
+//! The `dev_*` RPCs, only built with the `dev-tools` feature and only served on development
+//! chains sealed on demand.
+//!
+//! They drive the chain from tests and tooling: skip time, force a new session or era, and set
+//! balances. State changes go through `pallet_dev_tools`, which dispatches them as `Root`, so no
+//! sudo key is needed.
+
+use codec::Encode;
+use futures::{channel::oneshot, SinkExt};
+use jsonrpsee::{
+	core::async_trait,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+};
+use sc_consensus_manual_seal::rpc::EngineCommand;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_babe::BabeApi;
+use sp_core::H256;
+use sp_runtime::OpaqueExtrinsic;
+use std::{str::FromStr, sync::Arc};
+use taker_dev_runtime::{
+	opaque::Block, AccountId, AssetCurrencyCall, Balance, BalancesCall, DevToolsCall, RuntimeCall,
+	StakingCall, UncheckedExtrinsic,
+};
+
+use super::SealingDeps;
+
+#[derive(Debug, thiserror::Error)]
+/// Top-level error type for the RPC handler
+pub enum Error {
+	/// The account is not a valid H160 address.
+	#[error("parse account id failed")]
+	InvalidAccount,
+	/// The balance is not a decimal integer.
+	#[error("parse balance failed")]
+	InvalidBalance,
+	/// A runtime API call failed.
+	#[error("call api error")]
+	ApiCallErr(sp_api::ApiError),
+	/// The transaction pool rejected the call.
+	#[error("submit transaction error: {0}")]
+	Pool(String),
+	/// The block could not be sealed.
+	#[error("seal block error: {0}")]
+	Seal(String),
+}
+
+const DEV_ERROR: i32 = 8300;
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(error: Error) -> Self {
+		let code = match error {
+			Error::InvalidAccount => DEV_ERROR + 1,
+			Error::InvalidBalance => DEV_ERROR + 2,
+			Error::ApiCallErr(_) => DEV_ERROR + 3,
+			Error::Pool(_) => DEV_ERROR + 4,
+			Error::Seal(_) => DEV_ERROR + 5,
+		};
+		ErrorObject::owned(code, error.to_string(), None::<()>)
+	}
+}
+
+/// Every method seals at least one block and returns the hash of the last one.
+#[rpc(client, server)]
+pub trait DevApi {
+	/// Move the clock `millis` forward and seal a block at the new time.
+	#[method(name = "dev_advanceTime")]
+	async fn advance_time(&self, millis: u64) -> Result<H256, Error>;
+	/// Seal the first block of the next session.
+	#[method(name = "dev_newSession")]
+	async fn new_session(&self) -> Result<H256, Error>;
+	/// Force a new era and seal blocks until it is active.
+	#[method(name = "dev_newEra")]
+	async fn new_era(&self) -> Result<H256, Error>;
+	/// Set the free native balance of `account`.
+	#[method(name = "dev_setBalance")]
+	async fn set_balance(&self, account: String, free: String) -> Result<H256, Error>;
+	/// Set the free veTAKER balance of `account`.
+	#[method(name = "dev_setAssetBalance")]
+	async fn set_asset_balance(&self, account: String, free: String) -> Result<H256, Error>;
+}
+
+/// Implements the `dev_*` RPCs.
+pub struct Dev<C, P> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	sealing: SealingDeps,
+}
+
+impl<C, P> Dev<C, P> {
+	/// Create the RPC handler, sealing blocks through `sealing`.
+	pub fn new(client: Arc<C>, pool: Arc<P>, sealing: SealingDeps) -> Self {
+		Dev { client, pool, sealing }
+	}
+}
+
+impl<C, P> Dev<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BabeApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	async fn seal(&self) -> Result<H256, Error> {
+		let (sender, receiver) = oneshot::channel();
+		self.sealing
+			.command_sink
+			.clone()
+			.send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize: true,
+				parent_hash: None,
+				sender: Some(sender),
+			})
+			.await
+			.map_err(|e| Error::Seal(e.to_string()))?;
+		match receiver.await {
+			Ok(Ok(block)) => Ok(block.hash),
+			Ok(Err(e)) => Err(Error::Seal(e.to_string())),
+			Err(_) => Err(Error::Seal("block authoring stopped".to_string())),
+		}
+	}
+
+	/// Seal the block which starts the next BABE epoch, and so the next session.
+	async fn seal_next_session(&self) -> Result<H256, Error> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let epoch_start = api.current_epoch_start(best).map_err(Error::ApiCallErr)?;
+		let config = api.configuration(best).map_err(Error::ApiCallErr)?;
+		let next_epoch_start = u64::from(epoch_start) + config.epoch_length;
+		self.sealing.timestamp.skip_to(next_epoch_start * config.slot_duration);
+		self.seal().await
+	}
+
+	/// Dispatch `call` as `Root` in a new block.
+	async fn dispatch_as_root(&self, call: RuntimeCall) -> Result<H256, Error> {
+		let call = RuntimeCall::DevTools(DevToolsCall::dispatch_as_root { call: Box::new(call) });
+		let xt = OpaqueExtrinsic::from_bytes(&UncheckedExtrinsic::new_unsigned(call).encode())
+			.map_err(|e| Error::Pool(e.to_string()))?;
+		self.pool
+			.submit_one(self.client.info().best_hash, TransactionSource::Local, xt)
+			.await
+			.map_err(|e| Error::Pool(e.to_string()))?;
+		self.seal().await
+	}
+}
+
+fn parse_account(account: &str) -> Result<AccountId, Error> {
+	AccountId::from_str(account).map_err(|_| Error::InvalidAccount)
+}
+
+fn parse_balance(balance: &str) -> Result<Balance, Error> {
+	balance.parse().map_err(|_| Error::InvalidBalance)
+}
+
+#[async_trait]
+impl<C, P> DevApiServer for Dev<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BabeApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	async fn advance_time(&self, millis: u64) -> Result<H256, Error> {
+		self.sealing.timestamp.advance(millis);
+		self.seal().await
+	}
+
+	async fn new_session(&self) -> Result<H256, Error> {
+		self.seal_next_session().await
+	}
+
+	async fn new_era(&self) -> Result<H256, Error> {
+		self.dispatch_as_root(RuntimeCall::Staking(StakingCall::force_new_era {})).await?;
+		// The era is planned when the next session starts and becomes active one session later.
+		self.seal_next_session().await?;
+		self.seal_next_session().await
+	}
+
+	async fn set_balance(&self, account: String, free: String) -> Result<H256, Error> {
+		let who = parse_account(&account)?;
+		let new_free = parse_balance(&free)?;
+		self.dispatch_as_root(RuntimeCall::Balances(BalancesCall::force_set_balance {
+			who,
+			new_free,
+		}))
+		.await
+	}
+
+	async fn set_asset_balance(&self, account: String, free: String) -> Result<H256, Error> {
+		let who = parse_account(&account)?;
+		let new_free = parse_balance(&free)?;
+		self.dispatch_as_root(RuntimeCall::AssetCurrency(AssetCurrencyCall::force_set_balance {
+			who,
+			new_free,
+		}))
+		.await
+	}
+}
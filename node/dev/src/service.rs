@@ -1,19 +1,27 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 use bp_core::*;
-use futures::{FutureExt, StreamExt};
+use futures::{FutureExt, Stream, StreamExt};
 use jsonrpsee::RpcModule;
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{
+	collections::BTreeMap,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
 use fc_consensus::FrontierBlockImport;
 use taker_common_node::{
-	cli_opt::{EthApi as EthApiCmd, RpcConfig},
+	cli_opt::{EthApi as EthApiCmd, RpcConfig, Sealing},
 	rpc::{BabeDeps, FullDeps, GrandpaDeps, SpawnTasksParams, TracingConfig},
 	service::{open_frontier_backend, HostFunctions},
 	tracing::{spawn_tracing_tasks, RpcRequesters},
 };
 
-use crate::rpc::create_full;
+use crate::rpc::{create_full, SealingDeps};
 
 use fc_mapping_sync::{kv::MappingSyncWorker, SyncStrategy};
 use fc_rpc::{EthTask, StorageOverrideHandler};
@@ -22,6 +30,9 @@ use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
 use sc_consensus_babe::{BabeLink, BabeWorkerHandle};
 use sc_consensus_grandpa::GrandpaBlockImport;
+use sc_consensus_manual_seal::{
+	consensus::babe::BabeConsensusDataProvider, rpc::EngineCommand, ManualSealParams,
+};
 // use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 pub use sc_executor::WasmExecutor;
 use sc_network::service::traits::NetworkService;
@@ -31,7 +42,7 @@ use sc_service::{
 	error::Error as ServiceError, Configuration, RpcHandlers, SpawnTaskHandle, TaskManager,
 	WarpSyncParams,
 };
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool as _};
 
 use sc_consensus_babe::SlotProportion;
 use sc_telemetry::{Telemetry, TelemetryWorker};
@@ -75,9 +86,55 @@ type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 /// The transaction pool type definition.
 pub type TransactionPool = sc_transaction_pool::FullPool<Block, FullClient>;
 
+/// The timestamp of blocks sealed on demand.
+///
+/// Every block moves it one slot forward, so that BABE sees consecutive slots however fast blocks
+/// are sealed. It can be moved further to skip time.
+#[derive(Clone)]
+pub struct ManualTimestamp {
+	next: Arc<AtomicU64>,
+	slot_duration: u64,
+}
+
+impl ManualTimestamp {
+	/// Start from the current time, or right after the best block if it is ahead of it.
+	fn new(client: &FullClient, slot_duration: u64) -> Self {
+		use sp_blockchain::HeaderBackend;
+
+		let now = sp_timestamp::Timestamp::current().as_millis();
+		let after_best = client
+			.header(client.info().best_hash)
+			.ok()
+			.flatten()
+			.and_then(|header| sc_consensus_babe::find_pre_digest::<Block>(&header).ok())
+			.map(|pre_digest| (u64::from(pre_digest.slot()) + 1) * slot_duration)
+			.unwrap_or_default();
+		ManualTimestamp { next: Arc::new(AtomicU64::new(now.max(after_best))), slot_duration }
+	}
+
+	/// The timestamp of the next block. Moves the clock one slot forward.
+	fn next(&self) -> u64 {
+		self.next.fetch_add(self.slot_duration, Ordering::SeqCst)
+	}
+
+	/// Move the clock `millis` forward.
+	pub fn advance(&self, millis: u64) {
+		self.next.fetch_add(millis, Ordering::SeqCst);
+	}
+
+	/// Move the clock forward to `millis`, if it is behind it.
+	pub fn skip_to(&self, millis: u64) {
+		self.next.fetch_max(millis, Ordering::SeqCst);
+	}
+}
+
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration, rpc_config: RpcConfig) -> Result<TaskManager, ServiceError> {
-	new_full_base::<sc_network::NetworkWorker<_, _>>(config, rpc_config)
+pub fn new_full(
+	config: Configuration,
+	rpc_config: RpcConfig,
+	sealing: Option<Sealing>,
+) -> Result<TaskManager, ServiceError> {
+	new_full_base::<sc_network::NetworkWorker<_, _>>(config, rpc_config, sealing)
 		.map(|NewFullBase { task_manager, .. }| task_manager)
 }
 
@@ -249,6 +306,7 @@ pub fn new_partial(
 pub fn new_full_base<NB>(
 	config: Configuration,
 	rpc_config: RpcConfig,
+	sealing: Option<Sealing>,
 ) -> Result<NewFullBase, ServiceError>
 where
 	NB: sc_network::NetworkBackend<Block, <Block as BlockT>::Hash>,
@@ -343,9 +401,19 @@ where
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let is_authority = config.role.is_authority();
 
+	// When sealing on demand, blocks are authored by the manual seal task, driven by the
+	// `engine_*` RPCs and, depending on the mode, by the transaction pool or a timer.
+	let manual_seal = sealing.map(|sealing| {
+		let (command_sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let timestamp =
+			ManualTimestamp::new(&client, babe_link.config().slot_duration().as_millis());
+		(sealing, SealingDeps { command_sink, timestamp }, commands_stream)
+	});
+
 	let rpc_extensions_builder = build_rpc_extensions_builder(
 		&config,
 		rpc_config,
+		manual_seal.as_ref().map(|(_, sealing_deps, _)| sealing_deps.clone()),
 		RpcExtensionsBuilder {
 			spawn_handle: task_manager.spawn_handle(),
 			task_manager: &mut task_manager,
@@ -381,7 +449,74 @@ where
 	})
 	.ok();
 
-	if is_authority {
+	if let Some((sealing, SealingDeps { timestamp, .. }, commands_stream)) = manual_seal {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		let seal_new_block = |create_empty| EngineCommand::SealNewBlock {
+			create_empty,
+			finalize: true,
+			parent_hash: None,
+			sender: None,
+		};
+		let commands_stream: Pin<Box<dyn Stream<Item = EngineCommand<Hash>> + Send>> =
+			match sealing {
+				Sealing::Instant => Box::pin(futures::stream::select(
+					transaction_pool.import_notification_stream().map(move |_| seal_new_block(false)),
+					commands_stream,
+				)),
+				Sealing::Manual => Box::pin(commands_stream),
+				Sealing::Interval(millis) => Box::pin(futures::stream::select(
+					futures::stream::unfold((), move |()| async move {
+						tokio::time::sleep(Duration::from_millis(millis)).await;
+						Some((seal_new_block(true), ()))
+					}),
+					commands_stream,
+				)),
+			};
+
+		let slot_duration = babe_link.config().slot_duration();
+		let create_inherent_data_providers = move |_, ()| {
+			let timestamp = timestamp.clone();
+			async move {
+				let timestamp = sp_timestamp::InherentDataProvider::new(timestamp.next().into());
+				let slot = sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+					*timestamp,
+					slot_duration,
+				);
+				Ok((slot, timestamp))
+			}
+		};
+		let consensus_data_provider = BabeConsensusDataProvider::new(
+			client.clone(),
+			keystore_container.keystore(),
+			babe_link.epoch_changes().clone(),
+			babe_link.config().authorities.clone(),
+		)
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: babe_block_import,
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(consensus_data_provider)),
+			create_inherent_data_providers,
+		});
+
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+	} else if is_authority {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -438,7 +573,8 @@ where
 		protocol_name: grandpa_protocol_name,
 	};
 
-	if enable_grandpa {
+	// Blocks sealed on demand are finalized by the manual seal task.
+	if enable_grandpa && sealing.is_none() {
 		let grandpa_params = sc_consensus_grandpa::GrandpaParams {
 			config: grandpa_config,
 			link: grandpa_link,
@@ -466,6 +602,7 @@ where
 pub fn build_rpc_extensions_builder(
 	config: &Configuration,
 	rpc_config: RpcConfig,
+	sealing: Option<SealingDeps>,
 	builder: RpcExtensionsBuilder,
 ) -> impl Fn(DenyUnsafe, sc_rpc::SubscriptionTaskExecutor) -> Result<RpcModule<()>, sc_service::Error>
 {
@@ -666,10 +803,12 @@ pub fn build_rpc_extensions_builder(
 					trace_filter_max_count: rpc_config.ethapi_trace_max_count,
 				}),
 				pubsub_notification_sinks.clone(),
+				sealing.clone(),
 			)
 			.map_err(Into::into)
 		} else {
-			create_full(deps, None, pubsub_notification_sinks.clone()).map_err(Into::into)
+			create_full(deps, None, pubsub_notification_sinks.clone(), sealing.clone())
+				.map_err(Into::into)
 		}
 	};
 
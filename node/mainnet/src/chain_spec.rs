@@ -210,6 +210,7 @@ fn mainnet_genesis(
 		ethereum: Default::default(),
 		dynamic_fee: Default::default(),
 		base_fee: Default::default(),
+		fee_sharing: Default::default(),
 	};
 
 	serde_json::to_value(&config).expect("Could not build genesis config.")
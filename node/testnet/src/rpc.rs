@@ -20,7 +20,7 @@ use taker_common_node::{
 	cli_opt::EthApi as EthApiCmd,
 	rpc::{BabeDeps, DefaultEthConfig, TracingConfig},
 };
-use taker_testnet_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use taker_testnet_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce};
 
 use sc_client_api::{
 	backend::{Backend, StateBackend, StorageProvider},
@@ -30,7 +30,10 @@ pub use sc_client_api::{AuxStore, BlockOf, BlockchainEvents};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::TransactionPool;
-use taker_common_node::rpc::{staking::StakingApiServer, FullDeps, GrandpaDeps};
+use taker_common_node::rpc::{
+	asset_currency::AssetCurrencyRpcApiServer, provider::ProviderRpcApiServer,
+	staking::StakingApiServer, FullDeps, GrandpaDeps,
+};
 
 /// Instantiate all full RPC extensions.
 pub fn create_full<C, P, BE, SC, A, CIDP>(
@@ -61,6 +64,8 @@ where
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: fp_rpc_txpool::TxPoolRuntimeApi<Block>,
 	C::Api: pallet_staking_runtime_api::StakingRpcApi<Block>,
+	C::Api: pallet_asset_currency_runtime_api::AssetCurrencyApi<Block, AccountId, Balance>,
+	C::Api: pallet_provider_runtime_api::ProviderApi<Block, AccountId, Balance, BlockNumber>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 	SC: SelectChain<Block> + 'static,
@@ -167,7 +172,23 @@ where
 	io.merge(Web3::new(&client_version).into_rpc()).ok();
 
 	// pallet_staking rpc
-	io.merge(taker_common_node::rpc::staking::StakingClient::new(client.clone()).into_rpc())
+	io.merge(
+		taker_common_node::rpc::staking::StakingClient::new(
+			client.clone(),
+			Arc::clone(&subscription_executor),
+		)
+		.into_rpc(),
+	)
+	.ok();
+
+	// pallet_asset_currency rpc
+	io.merge(
+		taker_common_node::rpc::asset_currency::AssetCurrencyClient::new(client.clone()).into_rpc(),
+	)
+	.ok();
+
+	// pallet_provider rpc
+	io.merge(taker_common_node::rpc::provider::ProviderClient::new(client.clone()).into_rpc())
 		.ok();
 
 	io.merge(
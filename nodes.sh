@@ -13,6 +13,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 1200000000000000000000000000000000000000000000000000000011111111 \
   --telemetry-url 'wss://telemetry.polkadot.io/submit/ 0' \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
@@ -33,6 +34,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 0000000000000000000000000000000000000000000000000000000022222222 \
   --validator \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
@@ -54,6 +56,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 0000000000000000000000000000000000000000000000000000000033333333 \
   --validator \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
@@ -79,6 +82,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 1200000000000000000000000000000000000000000000000000000011111111 \
   --telemetry-url 'wss://telemetry.polkadot.io/submit/ 0' \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
@@ -96,6 +100,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 0000000000000000000000000000000000000000000000000000000022222222 \
   --validator \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
@@ -113,6 +118,7 @@ RUST_LOG=info nohup ./target/release/taker-node \
   --node-key 0000000000000000000000000000000000000000000000000000000033333333 \
   --validator \
   --enable-offchain-indexing true \
+  --offchain-worker always \
   --blocks-pruning=archive \
   --state-pruning=archive \
   --ethapi="debug,trace,txpool" \
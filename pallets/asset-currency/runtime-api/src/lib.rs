@@ -0,0 +1,26 @@
+//! Runtime API definition for the asset currency pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_asset_currency::types::{AccountData, BalanceLock, Reasons};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetCurrencyApi<AccountId, Balance>
+		where
+			AccountId: Codec,
+			Balance: Codec,
+	{
+		/// The free, reserved and frozen balance of an account.
+		fn account(who: AccountId) -> AccountData<Balance>;
+		/// The balance locks of an account.
+		fn locks(who: AccountId) -> Vec<BalanceLock<Balance>>;
+		/// The total issuance, and the part of it that is inactive.
+		fn issuance() -> (Balance, Balance);
+		/// The accounts allowed to mint and burn.
+		fn controllers() -> Vec<AccountId>;
+		/// The whitelisted accounts, with the whitelist admin.
+		fn whitelist() -> (Option<AccountId>, Vec<AccountId>);
+	}
+}
@@ -0,0 +1,14 @@
+//! Runtime API definition for the deployer allowlist pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::H160;
+
+sp_api::decl_runtime_apis! {
+	pub trait DeployerAllowlistApi {
+		/// Whether `deployer` may deploy contracts.
+		fn can_deploy(deployer: H160) -> bool;
+		/// The account which deployed `contract`.
+		fn deployer_of(contract: H160) -> Option<H160>;
+	}
+}
@@ -0,0 +1,235 @@
+//! Allowlist of the accounts which may deploy EVM contracts.
+//!
+//! While deployment is not open, only the deployers added by `ManagerOrigin` may create contracts.
+//! The runtime enforces this on Ethereum transactions and on `pallet_evm::create` and `create2`,
+//! see `Pallet::can_deploy`, and inside the EVM through `pallet_evm::EnsureCreateOrigin`. A
+//! contract creating contracts with CREATE or CREATE2 is their deployer, so factories must be
+//! allowed too. The deployer of every contract is recorded through `pallet_evm::OnCreate`,
+//! whether deployment is open or not.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_core::H160;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin which manages the allowlist.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Whether deployment is open until `ManagerOrigin` sets it.
+		#[pallet::constant]
+		type OpenByDefault: Get<bool>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultOpenDeployment<T: Config>() -> bool {
+		T::OpenByDefault::get()
+	}
+
+	/// Whether anyone may deploy contracts.
+	#[pallet::storage]
+	#[pallet::getter(fn open_deployment)]
+	pub type OpenDeployment<T: Config> =
+		StorageValue<_, bool, ValueQuery, DefaultOpenDeployment<T>>;
+
+	/// The accounts which may deploy contracts while deployment is not open.
+	#[pallet::storage]
+	#[pallet::getter(fn deployers)]
+	pub type Deployers<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// The account which deployed every contract.
+	#[pallet::storage]
+	#[pallet::getter(fn deployer_of)]
+	pub type DeployerOf<T: Config> = StorageMap<_, Blake2_128Concat, H160, H160, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account was allowed to deploy contracts.
+		DeployerAdded { deployer: H160 },
+		/// An account was no longer allowed to deploy contracts.
+		DeployerRemoved { deployer: H160 },
+		/// Deployment was opened to anyone, or restricted to the allowlist.
+		OpenDeploymentSet { open: bool },
+		/// A contract was deployed.
+		ContractDeployed { deployer: H160, contract: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already allowed to deploy contracts.
+		AlreadyAllowed,
+		/// The account is not allowed to deploy contracts.
+		NotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow `deployer` to deploy contracts.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(!Deployers::<T>::contains_key(deployer), Error::<T>::AlreadyAllowed);
+
+			Deployers::<T>::insert(deployer, ());
+			Self::deposit_event(Event::DeployerAdded { deployer });
+			Ok(())
+		}
+
+		/// Stop allowing `deployer` to deploy contracts. Its contracts are left in place.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_deployer(origin: OriginFor<T>, deployer: H160) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(Deployers::<T>::contains_key(deployer), Error::<T>::NotAllowed);
+
+			Deployers::<T>::remove(deployer);
+			Self::deposit_event(Event::DeployerRemoved { deployer });
+			Ok(())
+		}
+
+		/// Open deployment to anyone, or restrict it to the allowlist.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_open_deployment(origin: OriginFor<T>, open: bool) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			OpenDeployment::<T>::put(open);
+			Self::deposit_event(Event::OpenDeploymentSet { open });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `deployer` may deploy contracts.
+		pub fn can_deploy(deployer: &H160) -> bool {
+			Self::open_deployment() || Deployers::<T>::contains_key(deployer)
+		}
+	}
+
+	/// Used as both `CreateOriginFilter` and `CreateInnerOriginFilter`, so the allowlist also
+	/// applies to contracts created by contracts.
+	impl<T: Config + pallet_evm::Config> pallet_evm::EnsureCreateOrigin<T> for Pallet<T> {
+		fn check_create_origin(address: &H160) -> Result<(), pallet_evm::Error<T>> {
+			if !Self::can_deploy(address) {
+				return Err(pallet_evm::Error::<T>::CreateOriginNotAllowed);
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> pallet_evm::OnCreate<T> for Pallet<T> {
+		fn on_create(owner: H160, contract: H160) {
+			DeployerOf::<T>::insert(contract, owner);
+			Self::deposit_event(Event::ContractDeployed { deployer: owner, contract });
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_deployer_allowlist;
+	use frame_support::{assert_noop, assert_ok, derive_impl, parameter_types};
+	use pallet_evm::OnCreate;
+	use sp_core::H160;
+	use sp_runtime::{BuildStorage, DispatchError};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			DeployerAllowlist: pallet_deployer_allowlist,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+	}
+
+	parameter_types! {
+		pub static OpenByDefault: bool = false;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type ManagerOrigin = frame_system::EnsureRoot<u64>;
+		type OpenByDefault = OpenByDefault;
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	const DEPLOYER: H160 = H160::repeat_byte(1);
+	const OTHER: H160 = H160::repeat_byte(2);
+
+	#[test]
+	fn only_allowed_deployers_deploy_until_deployment_opens() {
+		new_test_ext().execute_with(|| {
+			assert!(!DeployerAllowlist::can_deploy(&DEPLOYER));
+			assert_noop!(
+				DeployerAllowlist::add_deployer(RuntimeOrigin::signed(1), DEPLOYER),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(DeployerAllowlist::add_deployer(RuntimeOrigin::root(), DEPLOYER));
+			assert_noop!(
+				DeployerAllowlist::add_deployer(RuntimeOrigin::root(), DEPLOYER),
+				Error::<Test>::AlreadyAllowed
+			);
+			assert!(DeployerAllowlist::can_deploy(&DEPLOYER));
+			assert!(!DeployerAllowlist::can_deploy(&OTHER));
+
+			assert_ok!(DeployerAllowlist::set_open_deployment(RuntimeOrigin::root(), true));
+			assert!(DeployerAllowlist::can_deploy(&OTHER));
+			assert_ok!(DeployerAllowlist::set_open_deployment(RuntimeOrigin::root(), false));
+			assert!(!DeployerAllowlist::can_deploy(&OTHER));
+
+			assert_ok!(DeployerAllowlist::remove_deployer(RuntimeOrigin::root(), DEPLOYER));
+			assert_noop!(
+				DeployerAllowlist::remove_deployer(RuntimeOrigin::root(), DEPLOYER),
+				Error::<Test>::NotAllowed
+			);
+			assert!(!DeployerAllowlist::can_deploy(&DEPLOYER));
+		});
+	}
+
+	#[test]
+	fn deployment_is_open_by_default_if_configured() {
+		OpenByDefault::set(true);
+		new_test_ext().execute_with(|| {
+			assert!(DeployerAllowlist::open_deployment());
+			assert!(DeployerAllowlist::can_deploy(&OTHER));
+		});
+		OpenByDefault::set(false);
+	}
+
+	#[test]
+	fn deployers_are_recorded() {
+		new_test_ext().execute_with(|| {
+			let contract = H160::repeat_byte(3);
+			<DeployerAllowlist as OnCreate<Test>>::on_create(DEPLOYER, contract);
+			assert_eq!(DeployerAllowlist::deployer_of(contract), Some(DEPLOYER));
+			System::assert_last_event(
+				Event::ContractDeployed { deployer: DEPLOYER, contract }.into(),
+			);
+		});
+	}
+}
@@ -0,0 +1,211 @@
+//! Development helpers for the dev runtime.
+//!
+//! Lets the node itself dispatch calls with `Root` origin through unsigned transactions, so
+//! tooling can force eras or set balances without holding the sudo key. Transactions are only
+//! accepted into the pool from the local node, never from the network. Blocks carrying them are
+//! still applied, which is sound on the dev chain since it only imports the blocks it authors.
+//!
+//! NEVER include this pallet in a production runtime. The dev runtime only includes it with the
+//! `dev-tools` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Dispatchable;
+	use sp_std::boxed::Box;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// A call which can be dispatched as `Root`.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was dispatched as `Root`.
+		Dispatched { result: DispatchResult },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatch `call` with `Root` origin.
+		///
+		/// The dispatch origin for this call must be _None_, and it is only accepted from the
+		/// local node.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(dispatch_info.weight, dispatch_info.class)
+		})]
+		pub fn dispatch_as_root(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let result = call.dispatch(frame_system::RawOrigin::Root.into());
+			Self::deposit_event(Event::Dispatched {
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+			// The fee is not paid by anyone, so the actual weight does not matter here.
+			Ok(Pays::No.into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			// A block carrying the transaction could come from anyone, so only the local pool
+			// accepts it.
+			if source != TransactionSource::Local {
+				return InvalidTransaction::Call.into();
+			}
+			match call {
+				Call::dispatch_as_root { call } => ValidTransaction::with_tag_prefix("DevTools")
+					.priority(TransactionPriority::MAX)
+					.and_provides((
+						frame_system::Pallet::<T>::block_number(),
+						call.using_encoded(sp_io::hashing::blake2_256),
+					))
+					.longevity(64)
+					.propagate(false)
+					.build(),
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+
+		// The default re-validates with `TransactionSource::InBlock`, which would drop the call
+		// from the block the local node builds.
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			match call {
+				Call::dispatch_as_root { .. } => Ok(()),
+				_ => Err(InvalidTransaction::Call.into()),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_dev_tools;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl,
+		pallet_prelude::{InvalidTransaction, TransactionSource},
+		unsigned::ValidateUnsigned,
+	};
+	use sp_runtime::{
+		traits::{Block as BlockT, Header as HeaderT},
+		BuildStorage, DispatchError,
+	};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+	type Extrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+	type Executive = frame_executive::Executive<
+		Test,
+		Block,
+		frame_system::ChainContext<Test>,
+		Test,
+		AllPalletsWithSystem,
+	>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			DevTools: pallet_dev_tools,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeCall = RuntimeCall;
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn set_storage() -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::System(frame_system::Call::set_storage {
+			items: vec![(b"key".to_vec(), b"value".to_vec())],
+		}))
+	}
+
+	#[test]
+	fn dispatches_as_root() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(DevTools::dispatch_as_root(RuntimeOrigin::none(), set_storage()));
+			assert_eq!(sp_io::storage::get(b"key").as_deref(), Some(&b"value"[..]));
+			System::assert_last_event(Event::Dispatched { result: Ok(()) }.into());
+		});
+	}
+
+	#[test]
+	fn rejects_signed_origin() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				DevTools::dispatch_as_root(RuntimeOrigin::signed(1), set_storage()),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn only_accepts_local_transactions() {
+		new_test_ext().execute_with(|| {
+			let call = Call::<Test>::dispatch_as_root { call: set_storage() };
+			assert!(DevTools::validate_unsigned(TransactionSource::Local, &call).is_ok());
+			assert_eq!(
+				DevTools::validate_unsigned(TransactionSource::External, &call),
+				InvalidTransaction::Call.into()
+			);
+		});
+	}
+
+	#[test]
+	fn applies_in_blocks() {
+		new_test_ext().execute_with(|| {
+			let header = <Block as BlockT>::Header::new(
+				1,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			);
+			Executive::initialize_block(&header);
+
+			let xt = Extrinsic::new_unsigned(RuntimeCall::DevTools(Call::dispatch_as_root {
+				call: set_storage(),
+			}));
+			assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+			assert_eq!(sp_io::storage::get(b"key").as_deref(), Some(&b"value"[..]));
+			System::assert_has_event(Event::Dispatched { result: Ok(()) }.into());
+		});
+	}
+}
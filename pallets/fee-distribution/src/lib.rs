@@ -0,0 +1,439 @@
+//! Distribution of the transaction fees.
+//!
+//! Every fee is split according to `Distribution`: a share is burned, and the rest goes to the
+//! author of the block, to `FeeCollector` and to `Treasury`. The priority fees of EVM transactions
+//! are split according to `TipDistribution` instead, which pays them all to the author of the
+//! block, as Frontier does, until `DistributionOrigin` sets it. Substrate tips are part of the fee.
+//!
+//! The runtime hands the fees of both Substrate and EVM transactions to `DistributeFees`, the
+//! fees charged in veTAKER to `Distribute` over veTAKER, and the EVM priority fees to
+//! `DistributeTips`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use scale_info::TypeInfo;
+use sp_runtime::{PerThing, Percent, RuntimeDebug};
+
+pub use pallet::*;
+
+/// The shares of every fee, which add up to 100%.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct FeeDistribution {
+	/// The share burned.
+	pub burn: Percent,
+	/// The share paid to the author of the block.
+	pub author: Percent,
+	/// The share paid to `FeeCollector`.
+	pub collector: Percent,
+	/// The share paid to `Treasury`, or to `FeeCollector` while it is not set.
+	pub treasury: Percent,
+}
+
+impl FeeDistribution {
+	/// Whether the shares add up to 100%.
+	pub fn is_complete(&self) -> bool {
+		[self.burn, self.author, self.collector, self.treasury]
+			.iter()
+			.map(|share| u16::from(share.deconstruct()))
+			.sum::<u16>() ==
+			100
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::FeeDistribution;
+	use frame_support::{pallet_prelude::*, traits::Currency};
+	use frame_system::pallet_prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_authorship::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The currency fees are charged in.
+		type Currency: Currency<Self::AccountId>;
+		/// The account the collector share goes to.
+		#[pallet::constant]
+		type FeeCollector: Get<Self::AccountId>;
+		/// The distribution of the fees until `DistributionOrigin` sets it.
+		#[pallet::constant]
+		type DefaultDistribution: Get<FeeDistribution>;
+		/// The distribution of the EVM priority fees until `DistributionOrigin` sets it.
+		#[pallet::constant]
+		type DefaultTipDistribution: Get<FeeDistribution>;
+		/// The origin which sets the distributions of the fees and the treasury.
+		type DistributionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultFeeDistribution<T: Config>() -> FeeDistribution {
+		T::DefaultDistribution::get()
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultTipDistribution<T: Config>() -> FeeDistribution {
+		T::DefaultTipDistribution::get()
+	}
+
+	/// The distribution of the fees.
+	#[pallet::storage]
+	#[pallet::getter(fn distribution)]
+	pub type Distribution<T: Config> =
+		StorageValue<_, FeeDistribution, ValueQuery, DefaultFeeDistribution<T>>;
+
+	/// The distribution of the EVM priority fees.
+	#[pallet::storage]
+	#[pallet::getter(fn tip_distribution)]
+	pub type TipDistribution<T: Config> =
+		StorageValue<_, FeeDistribution, ValueQuery, DefaultTipDistribution<T>>;
+
+	/// The account the treasury share goes to.
+	#[pallet::storage]
+	#[pallet::getter(fn treasury)]
+	pub type Treasury<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The distribution of the fees was set.
+		DistributionSet { distribution: FeeDistribution },
+		/// The distribution of the EVM priority fees was set.
+		TipDistributionSet { distribution: FeeDistribution },
+		/// The account the treasury share goes to was set.
+		TreasurySet { treasury: Option<T::AccountId> },
+		/// A fee was distributed. The author share goes to the collector when the block has no
+		/// known author, and so does the treasury share while `Treasury` is not set.
+		FeesDistributed {
+			burned: BalanceOf<T>,
+			author: Option<T::AccountId>,
+			to_author: BalanceOf<T>,
+			to_collector: BalanceOf<T>,
+			to_treasury: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The shares do not add up to 100%.
+		IncompleteDistribution,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the distribution of the fees.
+		///
+		/// The dispatch origin for this call must be `DistributionOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_distribution(
+			origin: OriginFor<T>,
+			distribution: FeeDistribution,
+		) -> DispatchResult {
+			T::DistributionOrigin::ensure_origin(origin)?;
+			ensure!(distribution.is_complete(), Error::<T>::IncompleteDistribution);
+
+			Distribution::<T>::put(distribution);
+			Self::deposit_event(Event::DistributionSet { distribution });
+			Ok(())
+		}
+
+		/// Set the distribution of the EVM priority fees.
+		///
+		/// The dispatch origin for this call must be `DistributionOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_tip_distribution(
+			origin: OriginFor<T>,
+			distribution: FeeDistribution,
+		) -> DispatchResult {
+			T::DistributionOrigin::ensure_origin(origin)?;
+			ensure!(distribution.is_complete(), Error::<T>::IncompleteDistribution);
+
+			TipDistribution::<T>::put(distribution);
+			Self::deposit_event(Event::TipDistributionSet { distribution });
+			Ok(())
+		}
+
+		/// Set the account the treasury share goes to, or unset it to pay the treasury share to
+		/// `FeeCollector`.
+		///
+		/// The dispatch origin for this call must be `DistributionOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_treasury(
+			origin: OriginFor<T>,
+			treasury: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::DistributionOrigin::ensure_origin(origin)?;
+
+			Treasury::<T>::set(treasury.clone());
+			Self::deposit_event(Event::TreasurySet { treasury });
+			Ok(())
+		}
+	}
+}
+
+/// Distributes the fees charged in `C` according to `Distribution`.
+pub struct Distribute<T, C>(PhantomData<(T, C)>);
+
+/// Distributes the fees charged in the native currency according to `Distribution`.
+pub type DistributeFees<T> = Distribute<T, <T as Config>::Currency>;
+
+impl<T, C> OnUnbalanced<C::NegativeImbalance> for Distribute<T, C>
+where
+	T: Config,
+	C: Currency<T::AccountId, Balance = BalanceOf<T>>,
+{
+	fn on_nonzero_unbalanced(fees: C::NegativeImbalance) {
+		distribute::<T, C>(fees, Pallet::<T>::distribution());
+	}
+}
+
+/// Distributes the EVM priority fees charged in `C`, the native currency by default, according
+/// to `TipDistribution`.
+pub struct DistributeTips<T, C = <T as Config>::Currency>(PhantomData<(T, C)>);
+
+impl<T, C> OnUnbalanced<C::NegativeImbalance> for DistributeTips<T, C>
+where
+	T: Config,
+	C: Currency<T::AccountId, Balance = BalanceOf<T>>,
+{
+	fn on_nonzero_unbalanced(tips: C::NegativeImbalance) {
+		distribute::<T, C>(tips, Pallet::<T>::tip_distribution());
+	}
+}
+
+fn distribute<T, C>(fees: C::NegativeImbalance, distribution: FeeDistribution)
+where
+	T: Config,
+	C: Currency<T::AccountId, Balance = BalanceOf<T>>,
+{
+	let total = fees.peek();
+	let author = pallet_authorship::Pallet::<T>::author();
+	let treasury = Pallet::<T>::treasury();
+
+	// The burned share is dropped, which reduces the total issuance.
+	let (burned, fees) = fees.split(distribution.burn.mul_floor(total));
+	let (to_author, fees) = match &author {
+		Some(_) => fees.split(distribution.author.mul_floor(total)),
+		None => (C::NegativeImbalance::zero(), fees),
+	};
+	let (to_treasury, to_collector) = match &treasury {
+		Some(_) => fees.split(distribution.treasury.mul_floor(total)),
+		None => (C::NegativeImbalance::zero(), fees),
+	};
+
+	let event = Event::FeesDistributed {
+		burned: burned.peek(),
+		author: author.clone(),
+		to_author: to_author.peek(),
+		to_collector: to_collector.peek(),
+		to_treasury: to_treasury.peek(),
+	};
+	if let Some(author) = author {
+		C::resolve_creating(&author, to_author);
+	}
+	if let Some(treasury) = treasury {
+		C::resolve_creating(&treasury, to_treasury);
+	}
+	C::resolve_creating(&T::FeeCollector::get(), to_collector);
+	Pallet::<T>::deposit_event(event);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_fee_distribution;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl, parameter_types,
+		traits::{ConstU64, FindAuthor},
+		ConsensusEngineId,
+	};
+	use sp_runtime::{BuildStorage, DispatchError};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Authorship: pallet_authorship,
+			Balances: pallet_balances,
+			FeeDistribution: pallet_fee_distribution,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	parameter_types! {
+		pub static BlockAuthor: Option<u64> = Some(AUTHOR);
+		pub const DefaultDistribution: FeeDistribution = distribution(0, 0, 100, 0);
+		pub const DefaultTipDistribution: FeeDistribution = distribution(0, 100, 0, 0);
+	}
+
+	pub struct StaticAuthor;
+	impl FindAuthor<u64> for StaticAuthor {
+		fn find_author<'a, I>(_: I) -> Option<u64>
+		where
+			I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+		{
+			BlockAuthor::get()
+		}
+	}
+
+	impl pallet_authorship::Config for Test {
+		type FindAuthor = StaticAuthor;
+		type EventHandler = ();
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Currency = Balances;
+		type FeeCollector = ConstU64<COLLECTOR>;
+		type DefaultDistribution = DefaultDistribution;
+		type DefaultTipDistribution = DefaultTipDistribution;
+		type DistributionOrigin = frame_system::EnsureRoot<u64>;
+	}
+
+	const AUTHOR: u64 = 1;
+	const COLLECTOR: u64 = 2;
+	const TREASURY: u64 = 3;
+
+	const fn distribution(burn: u8, author: u8, collector: u8, treasury: u8) -> FeeDistribution {
+		FeeDistribution {
+			burn: Percent::from_percent(burn),
+			author: Percent::from_percent(author),
+			collector: Percent::from_percent(collector),
+			treasury: Percent::from_percent(treasury),
+		}
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	#[test]
+	fn distributions_are_set_by_distribution_origin() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(FeeDistribution::distribution(), distribution(0, 0, 100, 0));
+			assert_eq!(FeeDistribution::tip_distribution(), distribution(0, 100, 0, 0));
+
+			assert_noop!(
+				FeeDistribution::set_distribution(
+					RuntimeOrigin::signed(AUTHOR),
+					distribution(10, 20, 30, 40)
+				),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				FeeDistribution::set_distribution(
+					RuntimeOrigin::root(),
+					distribution(10, 20, 30, 30)
+				),
+				Error::<Test>::IncompleteDistribution
+			);
+			assert_noop!(
+				FeeDistribution::set_tip_distribution(
+					RuntimeOrigin::root(),
+					distribution(0, 0, 0, 0)
+				),
+				Error::<Test>::IncompleteDistribution
+			);
+			assert_noop!(
+				FeeDistribution::set_treasury(RuntimeOrigin::signed(AUTHOR), Some(TREASURY)),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(FeeDistribution::set_distribution(
+				RuntimeOrigin::root(),
+				distribution(10, 20, 30, 40)
+			));
+			assert_ok!(FeeDistribution::set_tip_distribution(
+				RuntimeOrigin::root(),
+				distribution(50, 50, 0, 0)
+			));
+			assert_ok!(FeeDistribution::set_treasury(RuntimeOrigin::root(), Some(TREASURY)));
+			assert_eq!(FeeDistribution::distribution(), distribution(10, 20, 30, 40));
+			assert_eq!(FeeDistribution::tip_distribution(), distribution(50, 50, 0, 0));
+			assert_eq!(FeeDistribution::treasury(), Some(TREASURY));
+			System::assert_last_event(Event::TreasurySet { treasury: Some(TREASURY) }.into());
+		});
+	}
+
+	#[test]
+	fn fees_are_split_by_the_distribution() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeeDistribution::set_distribution(
+				RuntimeOrigin::root(),
+				distribution(10, 20, 30, 40)
+			));
+			assert_ok!(FeeDistribution::set_treasury(RuntimeOrigin::root(), Some(TREASURY)));
+
+			DistributeFees::<Test>::on_unbalanced(Balances::issue(1_000));
+			assert_eq!(Balances::free_balance(AUTHOR), 200);
+			assert_eq!(Balances::free_balance(COLLECTOR), 300);
+			assert_eq!(Balances::free_balance(TREASURY), 400);
+			assert_eq!(Balances::total_issuance(), 900);
+			System::assert_last_event(
+				Event::FeesDistributed {
+					burned: 100,
+					author: Some(AUTHOR),
+					to_author: 200,
+					to_collector: 300,
+					to_treasury: 400,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn missing_author_and_treasury_shares_go_to_the_collector() {
+		BlockAuthor::set(None);
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeeDistribution::set_distribution(
+				RuntimeOrigin::root(),
+				distribution(10, 20, 30, 40)
+			));
+
+			DistributeFees::<Test>::on_unbalanced(Balances::issue(1_000));
+			assert_eq!(Balances::free_balance(COLLECTOR), 900);
+			assert_eq!(Balances::free_balance(TREASURY), 0);
+			assert_eq!(Balances::total_issuance(), 900);
+		});
+		BlockAuthor::set(Some(AUTHOR));
+	}
+
+	#[test]
+	fn tips_go_to_the_author_by_default() {
+		new_test_ext().execute_with(|| {
+			DistributeTips::<Test>::on_unbalanced(Balances::issue(100));
+			DistributeFees::<Test>::on_unbalanced(Balances::issue(1_000));
+			assert_eq!(Balances::free_balance(AUTHOR), 100);
+			assert_eq!(Balances::free_balance(COLLECTOR), 1_000);
+		});
+	}
+}
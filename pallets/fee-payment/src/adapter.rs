@@ -0,0 +1,170 @@
+// The native path delegates to `CurrencyAdapter`, as the runtimes did before.
+#![allow(deprecated)]
+
+use crate::{AssetNegativeImbalanceOf, BalanceOf, Config, Pallet};
+use core::marker::PhantomData;
+use frame_support::traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons};
+use pallet_evm::{AddressMapping, EVMCurrencyAdapter, OnChargeEVMTransaction};
+use pallet_transaction_payment::{CurrencyAdapter, OnChargeTransaction};
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, UniqueSaturatedInto, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	FixedU128,
+};
+
+type NativeNegativeImbalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+type NativeAdapter<T, OU> = CurrencyAdapter<<T as Config>::NativeCurrency, OU>;
+type NativeEVMAdapter<T, OU> = EVMCurrencyAdapter<<T as Config>::NativeCurrency, OU>;
+
+/// The fee withdrawn from an account, in the currency it was charged in.
+pub enum FeeCredit<T: Config> {
+	/// Charged in TAKER.
+	Native(NativeNegativeImbalanceOf<T>),
+	/// Charged in veTAKER, at the given rate.
+	Asset(AssetNegativeImbalanceOf<T>, FixedU128),
+}
+
+/// Charges Substrate transaction fees in veTAKER for the accounts which chose to, and in TAKER,
+/// handled by `OU`, otherwise.
+pub struct TransactionFeeAdapter<T, OU>(PhantomData<(T, OU)>);
+
+impl<T, OU> OnChargeTransaction<T> for TransactionFeeAdapter<T, OU>
+where
+	T: Config + pallet_transaction_payment::Config,
+	OU: OnUnbalanced<NativeNegativeImbalanceOf<T>>,
+{
+	type Balance = BalanceOf<T>;
+	type LiquidityInfo = Option<FeeCredit<T>>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let Some(rate) = Pallet::<T>::asset_fee_rate_of(who) else {
+			return <NativeAdapter<T, OU> as OnChargeTransaction<T>>::withdraw_fee(
+				who, call, info, fee, tip,
+			)
+			.map(|paid| paid.map(FeeCredit::Native));
+		};
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let reasons = if tip.is_zero() {
+			WithdrawReasons::TRANSACTION_PAYMENT
+		} else {
+			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
+		};
+		Pallet::<T>::withdraw_asset_fee(who, fee, rate, reasons, ExistenceRequirement::KeepAlive)
+			.map(|paid| Some(FeeCredit::Asset(paid, rate)))
+			.map_err(|_| InvalidTransaction::Payment.into())
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		match already_withdrawn {
+			Some(FeeCredit::Native(paid)) =>
+				<NativeAdapter<T, OU> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+					who,
+					dispatch_info,
+					post_info,
+					corrected_fee,
+					tip,
+					Some(paid),
+				),
+			Some(FeeCredit::Asset(paid, rate)) => {
+				Pallet::<T>::settle_asset_fee(who, paid, corrected_fee, rate);
+				Ok(())
+			},
+			None => Ok(()),
+		}
+	}
+}
+
+/// Charges EVM transaction fees in veTAKER for the accounts which chose to, and in TAKER,
+/// handled by `OU`, otherwise. The priority fees charged in TAKER are handed to `OT` rather than
+/// issued to the block author as `EVMCurrencyAdapter` does, and those charged in veTAKER to
+/// `OnAssetTips`.
+pub struct EVMFeeAdapter<T, OU, OT>(PhantomData<(T, OU, OT)>);
+
+impl<T, OU, OT> OnChargeEVMTransaction<T> for EVMFeeAdapter<T, OU, OT>
+where
+	T: Config + pallet_evm::Config,
+	OU: OnUnbalanced<NativeNegativeImbalanceOf<T>>,
+	OT: OnUnbalanced<NativeNegativeImbalanceOf<T>>,
+	U256: UniqueSaturatedInto<BalanceOf<T>>,
+{
+	type LiquidityInfo = Option<FeeCredit<T>>;
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
+		let account_id = T::AddressMapping::into_account_id(*who);
+		let Some(rate) = Pallet::<T>::asset_fee_rate_of(&account_id) else {
+			return <NativeEVMAdapter<T, OU> as OnChargeEVMTransaction<T>>::withdraw_fee(who, fee)
+				.map(|paid| paid.map(FeeCredit::Native));
+		};
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		Pallet::<T>::withdraw_asset_fee(
+			&account_id,
+			fee.unique_saturated_into(),
+			rate,
+			WithdrawReasons::FEE,
+			ExistenceRequirement::AllowDeath,
+		)
+		.map(|paid| Some(FeeCredit::Asset(paid, rate)))
+		.map_err(|_| pallet_evm::Error::<T>::BalanceLow)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		match already_withdrawn {
+			Some(FeeCredit::Native(paid)) =>
+				<NativeEVMAdapter<T, OU> as OnChargeEVMTransaction<T>>::correct_and_deposit_fee(
+					who,
+					corrected_fee,
+					base_fee,
+					Some(paid),
+				)
+				.map(FeeCredit::Native),
+			Some(FeeCredit::Asset(paid, rate)) => {
+				let account_id = T::AddressMapping::into_account_id(*who);
+				let tip = Pallet::<T>::settle_asset_evm_fee(
+					&account_id,
+					paid,
+					corrected_fee.unique_saturated_into(),
+					base_fee.unique_saturated_into(),
+					rate,
+				);
+				Some(FeeCredit::Asset(tip, rate))
+			},
+			None => None,
+		}
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		match tip {
+			Some(FeeCredit::Native(tip)) => OT::on_unbalanced(tip),
+			Some(FeeCredit::Asset(tip, _)) => T::OnAssetTips::on_unbalanced(tip),
+			None => (),
+		}
+	}
+}
@@ -0,0 +1,470 @@
+//! Pay transaction fees in veTAKER.
+//!
+//! An account opts in with `set_fee_currency`. While `AssetFeeRate` is set, its Substrate and EVM
+//! transaction fees are then charged in veTAKER, `AssetFeeRate` veTAKER per TAKER of fee, and
+//! handed to `OnAssetFees`, with the EVM priority fees handed to `OnAssetTips`. Unused fees are
+//! refunded in veTAKER at the rate they were charged at. Otherwise, fees are charged in TAKER as
+//! usual.
+//!
+//! The runtime plugs the pallet in with `TransactionFeeAdapter` and `EVMFeeAdapter`. Frontier
+//! still checks that the TAKER balance of the sender covers the gas limit before running an EVM
+//! transaction, so an account paying in veTAKER must hold that much TAKER, which it keeps.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod adapter;
+
+pub use adapter::{EVMFeeAdapter, FeeCredit, TransactionFeeAdapter};
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{FixedPointNumber, FixedU128, Saturating};
+
+	pub type BalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type AssetNegativeImbalanceOf<T> = <<T as Config>::AssetCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The currency fees are charged in by default, TAKER.
+		type NativeCurrency: Currency<Self::AccountId>;
+		/// The currency fees may be charged in instead, veTAKER.
+		type AssetCurrency: Currency<Self::AccountId, Balance = BalanceOf<Self>>;
+		/// The handler for the fees charged in veTAKER.
+		type OnAssetFees: OnUnbalanced<AssetNegativeImbalanceOf<Self>>;
+		/// The handler for the EVM priority fees charged in veTAKER.
+		type OnAssetTips: OnUnbalanced<AssetNegativeImbalanceOf<Self>>;
+		/// The origin which sets the conversion rate.
+		type RateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The veTAKER charged per TAKER of fee. Fees are only charged in veTAKER while it is set.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_fee_rate)]
+	pub type AssetFeeRate<T: Config> = StorageValue<_, FixedU128, OptionQuery>;
+
+	/// The accounts which pay their fees in veTAKER.
+	#[pallet::storage]
+	#[pallet::getter(fn pays_in_asset)]
+	pub type PaysInAsset<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account chose the currency it pays its fees in.
+		FeeCurrencySet { who: T::AccountId, in_asset: bool },
+		/// The conversion rate was set, or unset to charge every fee in TAKER.
+		AssetFeeRateSet { rate: Option<FixedU128> },
+		/// An account paid `fee` TAKER of fees with `paid` veTAKER.
+		AssetFeePaid { who: T::AccountId, fee: BalanceOf<T>, paid: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The conversion rate is zero.
+		ZeroRate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay the fees of the origin in veTAKER, or in TAKER.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_fee_currency(origin: OriginFor<T>, in_asset: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if in_asset {
+				PaysInAsset::<T>::insert(&who, ());
+			} else {
+				PaysInAsset::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::FeeCurrencySet { who, in_asset });
+			Ok(())
+		}
+
+		/// Set the veTAKER charged per TAKER of fee, or unset it to charge every fee in TAKER.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_asset_fee_rate(origin: OriginFor<T>, rate: Option<FixedU128>) -> DispatchResult {
+			T::RateOrigin::ensure_origin(origin)?;
+			ensure!(rate.map_or(true, |rate| !rate.is_zero()), Error::<T>::ZeroRate);
+
+			AssetFeeRate::<T>::set(rate);
+			Self::deposit_event(Event::AssetFeeRateSet { rate });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The rate the fees of `who` are charged in veTAKER at, if they are.
+		pub fn asset_fee_rate_of(who: &T::AccountId) -> Option<FixedU128> {
+			Self::pays_in_asset(who).and(Self::asset_fee_rate())
+		}
+
+		/// Withdraw `fee` TAKER of fees from `who` in veTAKER at `rate`.
+		pub(crate) fn withdraw_asset_fee(
+			who: &T::AccountId,
+			fee: BalanceOf<T>,
+			rate: FixedU128,
+			reasons: WithdrawReasons,
+			liveness: ExistenceRequirement,
+		) -> Result<AssetNegativeImbalanceOf<T>, DispatchError> {
+			T::AssetCurrency::withdraw(who, rate.saturating_mul_int(fee), reasons, liveness)
+		}
+
+		/// Refund `who` what was withdrawn in `paid` beyond `fee` TAKER of fees at `rate`, and
+		/// hand the rest to `OnAssetFees`.
+		pub(crate) fn settle_asset_fee(
+			who: &T::AccountId,
+			paid: AssetNegativeImbalanceOf<T>,
+			fee: BalanceOf<T>,
+			rate: FixedU128,
+		) {
+			T::OnAssetFees::on_unbalanced(Self::refund_asset_fee(who, paid, fee, rate));
+		}
+
+		/// Refund `who` what was withdrawn in `paid` beyond `fee` TAKER of fees at `rate`, hand
+		/// `base_fee` TAKER of it to `OnAssetFees`, and return the rest, the priority fee.
+		pub(crate) fn settle_asset_evm_fee(
+			who: &T::AccountId,
+			paid: AssetNegativeImbalanceOf<T>,
+			fee: BalanceOf<T>,
+			base_fee: BalanceOf<T>,
+			rate: FixedU128,
+		) -> AssetNegativeImbalanceOf<T> {
+			let paid = Self::refund_asset_fee(who, paid, fee, rate);
+			let (base_fee, tip) = paid.split(rate.saturating_mul_int(base_fee));
+			T::OnAssetFees::on_unbalanced(base_fee);
+			tip
+		}
+
+		/// Refund `who` what was withdrawn in `paid` beyond `fee` TAKER of fees at `rate`, and
+		/// return the rest.
+		fn refund_asset_fee(
+			who: &T::AccountId,
+			paid: AssetNegativeImbalanceOf<T>,
+			fee: BalanceOf<T>,
+			rate: FixedU128,
+		) -> AssetNegativeImbalanceOf<T> {
+			let refund = paid.peek().saturating_sub(rate.saturating_mul_int(fee));
+			let refund = T::AssetCurrency::deposit_creating(who, refund);
+			let paid = paid
+				.offset(refund)
+				.same()
+				.unwrap_or_else(|_| AssetNegativeImbalanceOf::<T>::zero());
+
+			Self::deposit_event(Event::AssetFeePaid { who: who.clone(), fee, paid: paid.peek() });
+			paid
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_fee_payment;
+	use core::marker::PhantomData;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl, parameter_types,
+		traits::{
+			ConstU32, ConstU64, Currency, ExistenceRequirement, Get, OnUnbalanced,
+			WithdrawReasons,
+		},
+		weights::Weight,
+	};
+	use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot, OnChargeEVMTransaction};
+	use sp_core::{H160, U256};
+	use sp_runtime::{BuildStorage, DispatchError, FixedPointNumber, FixedU128};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+	type AssetInstance = pallet_balances::Instance2;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Timestamp: pallet_timestamp,
+			Balances: pallet_balances,
+			AssetBalances: pallet_balances::<Instance2>,
+			EVM: pallet_evm,
+			FeePayment: pallet_fee_payment,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	impl pallet_balances::Config<AssetInstance> for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeHoldReason = RuntimeHoldReason;
+		type RuntimeFreezeReason = RuntimeFreezeReason;
+		type WeightInfo = ();
+		type Balance = u64;
+		type DustRemoval = ();
+		type ExistentialDeposit = ConstU64<1>;
+		type AccountStore = frame_support::traits::StorageMapShim<
+			pallet_balances::Account<Test, AssetInstance>,
+			u64,
+			pallet_balances::AccountData<u64>,
+		>;
+		type ReserveIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxLocks = ConstU32<100>;
+		type MaxReserves = ConstU32<100>;
+		type MaxFreezes = ConstU32<100>;
+	}
+
+	#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+	impl pallet_timestamp::Config for Test {}
+
+	/// Maps an EVM address to the account of its low 8 bytes.
+	pub struct LowBytesMapping;
+	impl AddressMapping<u64> for LowBytesMapping {
+		fn into_account_id(address: H160) -> u64 {
+			address.to_low_u64_be()
+		}
+	}
+
+	parameter_types! {
+		pub BlockGasLimit: U256 = U256::from(u64::MAX);
+		pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+	}
+
+	impl pallet_evm::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Currency = Balances;
+		type BlockGasLimit = BlockGasLimit;
+		type ChainId = ConstU64<42>;
+		type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+		type Runner = pallet_evm::runner::stack::Runner<Self>;
+		type CallOrigin = EnsureAddressRoot<u64>;
+		type WithdrawOrigin = EnsureAddressNever<u64>;
+		type AddressMapping = LowBytesMapping;
+		type FeeCalculator = ();
+		type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+		type WeightPerGas = WeightPerGas;
+		type OnChargeTransaction = EVMAdapter;
+		type FindAuthor = ();
+		type PrecompilesType = ();
+		type PrecompilesValue = ();
+		type OnCreate = ();
+		type CreateOriginFilter = ();
+		type CreateInnerOriginFilter = ();
+		type GasLimitPovSizeRatio = ConstU64<4>;
+		type Timestamp = Timestamp;
+		type SuicideQuickClearLimit = ConstU32<0>;
+		type WeightInfo = ();
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type NativeCurrency = Balances;
+		type AssetCurrency = AssetBalances;
+		type OnAssetFees = ResolveTo<AssetBalances, ConstU64<COLLECTOR>>;
+		type OnAssetTips = ResolveTo<AssetBalances, ConstU64<AUTHOR>>;
+		type RateOrigin = frame_system::EnsureRoot<u64>;
+	}
+
+	type EVMAdapter = EVMFeeAdapter<
+		Test,
+		ResolveTo<Balances, ConstU64<COLLECTOR>>,
+		ResolveTo<Balances, ConstU64<AUTHOR>>,
+	>;
+
+	const ALICE: u64 = 1;
+	const AUTHOR: u64 = 98;
+	const COLLECTOR: u64 = 99;
+
+	/// Resolves what it is handed in `C` to the account `A`.
+	pub struct ResolveTo<C, A>(PhantomData<(C, A)>);
+	impl<C: Currency<u64>, A: Get<u64>> OnUnbalanced<C::NegativeImbalance> for ResolveTo<C, A> {
+		fn on_nonzero_unbalanced(amount: C::NegativeImbalance) {
+			C::resolve_creating(&A::get(), amount);
+		}
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 1_000)] }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		pallet_balances::GenesisConfig::<Test, AssetInstance> { balances: vec![(ALICE, 1_000)] }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn rate(n: u128, d: u128) -> FixedU128 {
+		FixedU128::checked_from_rational(n, d).unwrap()
+	}
+
+	fn withdraw(fee: u64, rate: FixedU128) -> AssetNegativeImbalanceOf<Test> {
+		FeePayment::withdraw_asset_fee(
+			&ALICE,
+			fee,
+			rate,
+			WithdrawReasons::TRANSACTION_PAYMENT,
+			ExistenceRequirement::KeepAlive,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn fees_are_charged_in_asset_only_when_opted_in_and_rate_set() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeePayment::set_fee_currency(RuntimeOrigin::signed(ALICE), true));
+			assert_eq!(FeePayment::asset_fee_rate_of(&ALICE), None);
+
+			assert_noop!(
+				FeePayment::set_asset_fee_rate(RuntimeOrigin::signed(ALICE), Some(rate(2, 1))),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				FeePayment::set_asset_fee_rate(RuntimeOrigin::root(), Some(rate(0, 1))),
+				Error::<Test>::ZeroRate
+			);
+			assert_ok!(FeePayment::set_asset_fee_rate(RuntimeOrigin::root(), Some(rate(2, 1))));
+			assert_eq!(FeePayment::asset_fee_rate_of(&ALICE), Some(rate(2, 1)));
+			assert_eq!(FeePayment::asset_fee_rate_of(&2), None);
+
+			assert_ok!(FeePayment::set_fee_currency(RuntimeOrigin::signed(ALICE), false));
+			assert_eq!(FeePayment::asset_fee_rate_of(&ALICE), None);
+			System::assert_last_event(Event::FeeCurrencySet { who: ALICE, in_asset: false }.into());
+
+			assert_ok!(FeePayment::set_fee_currency(RuntimeOrigin::signed(ALICE), true));
+			assert_ok!(FeePayment::set_asset_fee_rate(RuntimeOrigin::root(), None));
+			assert_eq!(FeePayment::asset_fee_rate_of(&ALICE), None);
+		});
+	}
+
+	#[test]
+	fn unused_fees_are_refunded_at_the_rate_charged() {
+		new_test_ext().execute_with(|| {
+			let issuance = AssetBalances::total_issuance();
+
+			let paid = withdraw(100, rate(2, 1));
+			assert_eq!(AssetBalances::free_balance(ALICE), 800);
+
+			FeePayment::settle_asset_fee(&ALICE, paid, 60, rate(2, 1));
+			assert_eq!(AssetBalances::free_balance(ALICE), 880);
+			assert_eq!(AssetBalances::free_balance(COLLECTOR), 120);
+			assert_eq!(AssetBalances::total_issuance(), issuance);
+			System::assert_last_event(
+				Event::AssetFeePaid { who: ALICE, fee: 60, paid: 120 }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn fractional_rates_round_down() {
+		new_test_ext().execute_with(|| {
+			let paid = withdraw(7, rate(3, 2));
+			assert_eq!(AssetBalances::free_balance(ALICE), 990);
+
+			FeePayment::settle_asset_fee(&ALICE, paid, 5, rate(3, 2));
+			assert_eq!(AssetBalances::free_balance(ALICE), 993);
+			assert_eq!(AssetBalances::free_balance(COLLECTOR), 7);
+		});
+	}
+
+	#[test]
+	fn fees_above_the_withdrawn_amount_are_capped() {
+		new_test_ext().execute_with(|| {
+			let paid = withdraw(100, rate(2, 1));
+
+			FeePayment::settle_asset_fee(&ALICE, paid, 150, rate(2, 1));
+			assert_eq!(AssetBalances::free_balance(ALICE), 800);
+			assert_eq!(AssetBalances::free_balance(COLLECTOR), 200);
+			System::assert_last_event(
+				Event::AssetFeePaid { who: ALICE, fee: 150, paid: 200 }.into(),
+			);
+		});
+	}
+
+	fn opt_in(rate: FixedU128) {
+		assert_ok!(FeePayment::set_fee_currency(RuntimeOrigin::signed(ALICE), true));
+		assert_ok!(FeePayment::set_asset_fee_rate(RuntimeOrigin::root(), Some(rate)));
+	}
+
+	#[test]
+	fn evm_fees_are_charged_in_asset_and_refunded() {
+		new_test_ext().execute_with(|| {
+			opt_in(rate(2, 1));
+			let alice = H160::from_low_u64_be(ALICE);
+			let issuance = AssetBalances::total_issuance();
+
+			let paid = EVMAdapter::withdraw_fee(&alice, U256::from(100)).unwrap();
+			assert_eq!(AssetBalances::free_balance(ALICE), 800);
+			assert_eq!(Balances::free_balance(ALICE), 1_000);
+
+			// 60 TAKER of fees were used, 40 of which are the base fee.
+			let tip =
+				EVMAdapter::correct_and_deposit_fee(&alice, U256::from(60), U256::from(40), paid);
+			assert_eq!(AssetBalances::free_balance(ALICE), 880);
+			assert_eq!(AssetBalances::free_balance(COLLECTOR), 80);
+			System::assert_last_event(
+				Event::AssetFeePaid { who: ALICE, fee: 60, paid: 120 }.into(),
+			);
+
+			EVMAdapter::pay_priority_fee(tip);
+			assert_eq!(AssetBalances::free_balance(AUTHOR), 40);
+			assert_eq!(AssetBalances::total_issuance(), issuance);
+			assert_eq!(Balances::free_balance(ALICE), 1_000);
+		});
+	}
+
+	#[test]
+	fn evm_fees_are_charged_in_native_otherwise() {
+		new_test_ext().execute_with(|| {
+			let alice = H160::from_low_u64_be(ALICE);
+
+			let paid = EVMAdapter::withdraw_fee(&alice, U256::from(100)).unwrap();
+			assert_eq!(Balances::free_balance(ALICE), 900);
+
+			let tip =
+				EVMAdapter::correct_and_deposit_fee(&alice, U256::from(60), U256::from(40), paid);
+			assert_eq!(Balances::free_balance(ALICE), 940);
+			assert_eq!(Balances::free_balance(COLLECTOR), 40);
+
+			EVMAdapter::pay_priority_fee(tip);
+			assert_eq!(Balances::free_balance(AUTHOR), 20);
+			assert_eq!(AssetBalances::free_balance(ALICE), 1_000);
+		});
+	}
+
+	#[test]
+	fn evm_fees_above_the_asset_balance_are_rejected() {
+		new_test_ext().execute_with(|| {
+			opt_in(rate(20, 1));
+			let alice = H160::from_low_u64_be(ALICE);
+
+			assert!(EVMAdapter::withdraw_fee(&alice, U256::from(51)).is_err());
+			assert_eq!(AssetBalances::free_balance(ALICE), 1_000);
+			assert_eq!(Balances::free_balance(ALICE), 1_000);
+		});
+	}
+}
@@ -0,0 +1,480 @@
+//! Developer gas rebates.
+//!
+//! The deployer of a contract, as recorded by `pallet_deployer_allowlist`, or `RegisterOrigin`
+//! may register it with a beneficiary. `DeveloperShare` of the EVM fees of every Ethereum
+//! transaction calling a registered contract then accrues to its beneficiary, who claims it from
+//! the pallet account. The pallet account is endowed with the existential deposit, at genesis or
+//! by `migrations::v1`, so that rebates below it are not lost.
+//!
+//! The runtime hands the EVM fees to `ShareFees`, and applies Ethereum transactions within
+//! `Pallet::with_target` so that the contract they call is known. The target is held in memory
+//! for the time the transaction is applied, and never written to storage. Only the fees charged
+//! in the native currency are shared, priority fees included.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod migrations;
+
+use core::marker::PhantomData;
+use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	PerThing,
+};
+
+pub use pallet::*;
+
+environmental::environmental!(current_target: Option<sp_core::H160>);
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement::AllowDeath, StorageVersion},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_evm::AddressMapping;
+	use sp_core::H160;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Zero},
+		Percent,
+	};
+
+	pub type BalanceOf<T> = <<T as pallet_evm::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as pallet_evm::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_evm::Config + pallet_deployer_allowlist::Config
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The pallet id, used to derive the account rebates are held in until claimed.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The share of the fees accrued to beneficiaries until `ShareOrigin` sets it.
+		#[pallet::constant]
+		type DefaultShare: Get<Percent>;
+		/// The origin which sets the share of the fees accrued to beneficiaries.
+		type ShareOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The origin which registers and unregisters any contract, such as one created before
+		/// deployers were recorded, or by another contract.
+		type RegisterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultDeveloperShare<T: Config>() -> Percent {
+		T::DefaultShare::get()
+	}
+
+	/// The share of the EVM fees paid to call a registered contract accrued to its beneficiary.
+	#[pallet::storage]
+	#[pallet::getter(fn developer_share)]
+	pub type DeveloperShare<T: Config> =
+		StorageValue<_, Percent, ValueQuery, DefaultDeveloperShare<T>>;
+
+	/// The beneficiary of every registered contract.
+	#[pallet::storage]
+	#[pallet::getter(fn beneficiary_of)]
+	pub type Beneficiaries<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, T::AccountId, OptionQuery>;
+
+	/// The rebates accrued to every beneficiary and not claimed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn accrued)]
+	pub type Accrued<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		#[serde(skip)]
+		pub _config: core::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Pallet::<T>::endow_account();
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A contract was registered, or its beneficiary changed.
+		ContractRegistered { contract: H160, beneficiary: T::AccountId },
+		/// A contract was unregistered.
+		ContractUnregistered { contract: H160 },
+		/// A share of the fees paid to call a contract accrued to its beneficiary.
+		RebateAccrued { contract: H160, beneficiary: T::AccountId, amount: BalanceOf<T> },
+		/// A beneficiary claimed its rebates.
+		RebateClaimed { who: T::AccountId, amount: BalanceOf<T> },
+		/// The share of the fees accrued to beneficiaries was set.
+		DeveloperShareSet { share: Percent },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account did not deploy the contract.
+		NotDeployer,
+		/// The contract is not registered.
+		NotRegistered,
+		/// The account has no rebates to claim.
+		NothingToClaim,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Accrue a share of the fees paid to call `contract` to `beneficiary`, or change its
+		/// beneficiary.
+		///
+		/// The dispatch origin for this call must be the deployer of `contract`, or
+		/// `RegisterOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn register(
+			origin: OriginFor<T>,
+			contract: H160,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_registrar(origin, contract)?;
+
+			Beneficiaries::<T>::insert(contract, beneficiary.clone());
+			Self::deposit_event(Event::ContractRegistered { contract, beneficiary });
+			Ok(())
+		}
+
+		/// Stop accruing a share of the fees paid to call `contract`. The rebates accrued so far
+		/// remain claimable.
+		///
+		/// The dispatch origin for this call must be the deployer of `contract`, or
+		/// `RegisterOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+		pub fn unregister(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			Self::ensure_registrar(origin, contract)?;
+			ensure!(Beneficiaries::<T>::contains_key(contract), Error::<T>::NotRegistered);
+
+			Beneficiaries::<T>::remove(contract);
+			Self::deposit_event(Event::ContractUnregistered { contract });
+			Ok(())
+		}
+
+		/// Claim the rebates accrued to the origin.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let amount = Accrued::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			T::Currency::transfer(&Self::account_id(), &who, amount, AllowDeath)?;
+			Self::deposit_event(Event::RebateClaimed { who, amount });
+			Ok(())
+		}
+
+		/// Set the share of the fees accrued to beneficiaries.
+		///
+		/// The dispatch origin for this call must be `ShareOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_developer_share(origin: OriginFor<T>, share: Percent) -> DispatchResult {
+			T::ShareOrigin::ensure_origin(origin)?;
+
+			DeveloperShare::<T>::put(share);
+			Self::deposit_event(Event::DeveloperShareSet { share });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account rebates are held in until claimed.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Apply, with `apply`, an Ethereum transaction calling `target`, so that its fees are
+		/// shared with the beneficiary of `target`.
+		pub fn with_target<R>(mut target: Option<H160>, apply: impl FnOnce() -> R) -> R {
+			super::current_target::using(&mut target, apply)
+		}
+
+		/// The contract called by the Ethereum transaction being applied, if any.
+		pub fn current_target() -> Option<H160> {
+			super::current_target::with(|target| *target).flatten()
+		}
+
+		/// Endow the pallet account with the existential deposit, unless it already holds it.
+		pub(crate) fn endow_account() {
+			let account = Self::account_id();
+			let minimum = T::Currency::minimum_balance();
+			if T::Currency::free_balance(&account) < minimum {
+				let _ = T::Currency::make_free_balance_be(&account, minimum);
+			}
+		}
+
+		fn ensure_registrar(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			let Err(origin) = T::RegisterOrigin::try_origin(origin) else {
+				return Ok(());
+			};
+			let who = ensure_signed(origin)?;
+			let deployer = pallet_deployer_allowlist::Pallet::<T>::deployer_of(contract)
+				.ok_or(Error::<T>::NotDeployer)?;
+			ensure!(T::AddressMapping::into_account_id(deployer) == who, Error::<T>::NotDeployer);
+			Ok(())
+		}
+	}
+}
+
+/// Accrues `DeveloperShare` of the EVM fees of the transaction being applied to the beneficiary
+/// of the contract it calls, if registered, and hands the rest to `OU`.
+pub struct ShareFees<T, OU>(PhantomData<(T, OU)>);
+
+impl<T, OU> OnUnbalanced<NegativeImbalanceOf<T>> for ShareFees<T, OU>
+where
+	T: Config,
+	OU: OnUnbalanced<NegativeImbalanceOf<T>>,
+{
+	fn on_nonzero_unbalanced(fees: NegativeImbalanceOf<T>) {
+		let Some((contract, beneficiary)) = Pallet::<T>::current_target().and_then(|contract| {
+			Beneficiaries::<T>::get(contract).map(|beneficiary| (contract, beneficiary))
+		}) else {
+			return OU::on_unbalanced(fees);
+		};
+
+		let amount = Pallet::<T>::developer_share().mul_floor(fees.peek());
+		if amount.is_zero() {
+			return OU::on_unbalanced(fees);
+		}
+		let (rebate, rest) = fees.split(amount);
+		<T as pallet_evm::Config>::Currency::resolve_creating(&Pallet::<T>::account_id(), rebate);
+		Accrued::<T>::mutate(&beneficiary, |accrued| accrued.saturating_accrue(amount));
+		Pallet::<T>::deposit_event(Event::RebateAccrued { contract, beneficiary, amount });
+		OU::on_unbalanced(rest);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_fee_sharing;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl, parameter_types,
+		traits::{ConstU32, ConstU64, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+		PalletId,
+	};
+	use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, IdentityAddressMapping};
+	use sp_core::{H160, U256};
+	use sp_runtime::{traits::IdentityLookup, BuildStorage, DispatchError, Percent};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Timestamp: pallet_timestamp,
+			Balances: pallet_balances,
+			EVM: pallet_evm,
+			DeployerAllowlist: pallet_deployer_allowlist,
+			FeeSharing: pallet_fee_sharing,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountId = H160;
+		type Lookup = IdentityLookup<H160>;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+	impl pallet_timestamp::Config for Test {}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type ExistentialDeposit = ConstU64<10>;
+		type AccountStore = System;
+	}
+
+	parameter_types! {
+		pub BlockGasLimit: U256 = U256::from(u64::MAX);
+		pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+	}
+
+	impl pallet_evm::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Currency = Balances;
+		type BlockGasLimit = BlockGasLimit;
+		type ChainId = ConstU64<42>;
+		type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+		type Runner = pallet_evm::runner::stack::Runner<Self>;
+		type CallOrigin = EnsureAddressRoot<H160>;
+		type WithdrawOrigin = EnsureAddressNever<H160>;
+		type AddressMapping = IdentityAddressMapping;
+		type FeeCalculator = ();
+		type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+		type WeightPerGas = WeightPerGas;
+		type OnChargeTransaction = ();
+		type FindAuthor = ();
+		type PrecompilesType = ();
+		type PrecompilesValue = ();
+		type OnCreate = DeployerAllowlist;
+		type CreateOriginFilter = ();
+		type CreateInnerOriginFilter = ();
+		type GasLimitPovSizeRatio = ConstU64<4>;
+		type Timestamp = Timestamp;
+		type SuicideQuickClearLimit = ConstU32<0>;
+		type WeightInfo = ();
+	}
+
+	impl pallet_deployer_allowlist::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type ManagerOrigin = frame_system::EnsureRoot<H160>;
+		type OpenByDefault = frame_support::traits::ConstBool<true>;
+	}
+
+	parameter_types! {
+		pub const FeeSharingPalletId: PalletId = PalletId(*b"feeshare");
+		pub const DefaultShare: Percent = Percent::from_percent(10);
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type PalletId = FeeSharingPalletId;
+		type DefaultShare = DefaultShare;
+		type ShareOrigin = frame_system::EnsureRoot<H160>;
+		type RegisterOrigin = frame_system::EnsureRoot<H160>;
+	}
+
+	const DEPLOYER: H160 = H160::repeat_byte(1);
+	const BENEFICIARY: H160 = H160::repeat_byte(2);
+	const CONTRACT: H160 = H160::repeat_byte(3);
+	const OTHER_CONTRACT: H160 = H160::repeat_byte(4);
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(DEPLOYER, 1_000), (BENEFICIARY, 1_000)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_fee_sharing::GenesisConfig::<Test>::default()
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			pallet_deployer_allowlist::DeployerOf::<Test>::insert(CONTRACT, DEPLOYER);
+		});
+		ext
+	}
+
+	fn register_contract() {
+		assert_ok!(FeeSharing::register(RuntimeOrigin::signed(DEPLOYER), CONTRACT, BENEFICIARY));
+	}
+
+	fn share(fees: u64) {
+		ShareFees::<Test, ()>::on_unbalanced(Balances::issue(fees));
+	}
+
+	#[test]
+	fn deployers_and_register_origin_register_contracts() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				FeeSharing::register(RuntimeOrigin::signed(BENEFICIARY), CONTRACT, BENEFICIARY),
+				Error::<Test>::NotDeployer
+			);
+			assert_noop!(
+				FeeSharing::register(RuntimeOrigin::signed(DEPLOYER), OTHER_CONTRACT, BENEFICIARY),
+				Error::<Test>::NotDeployer
+			);
+
+			assert_ok!(FeeSharing::register(
+				RuntimeOrigin::signed(DEPLOYER),
+				CONTRACT,
+				BENEFICIARY
+			));
+			assert_eq!(FeeSharing::beneficiary_of(CONTRACT), Some(BENEFICIARY));
+			assert_ok!(FeeSharing::register(RuntimeOrigin::root(), OTHER_CONTRACT, BENEFICIARY));
+			assert_eq!(FeeSharing::beneficiary_of(OTHER_CONTRACT), Some(BENEFICIARY));
+
+			assert_ok!(FeeSharing::unregister(RuntimeOrigin::root(), CONTRACT));
+			assert_noop!(
+				FeeSharing::unregister(RuntimeOrigin::signed(DEPLOYER), CONTRACT),
+				Error::<Test>::NotRegistered
+			);
+			assert_noop!(
+				FeeSharing::unregister(RuntimeOrigin::none(), OTHER_CONTRACT),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn fees_are_shared_with_the_target_beneficiary() {
+		new_test_ext().execute_with(|| {
+			register_contract();
+			assert_eq!(Balances::free_balance(FeeSharing::account_id()), 10);
+
+			share(1_000);
+			FeeSharing::with_target(Some(OTHER_CONTRACT), || share(1_000));
+			assert_eq!(FeeSharing::accrued(BENEFICIARY), 0);
+
+			FeeSharing::with_target(Some(CONTRACT), || {
+				assert_eq!(FeeSharing::current_target(), Some(CONTRACT));
+				share(1_000);
+			});
+			assert_eq!(FeeSharing::current_target(), None);
+			assert_eq!(FeeSharing::accrued(BENEFICIARY), 100);
+			assert_eq!(Balances::free_balance(FeeSharing::account_id()), 110);
+			System::assert_last_event(
+				Event::RebateAccrued { contract: CONTRACT, beneficiary: BENEFICIARY, amount: 100 }
+					.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn rebates_below_the_existential_deposit_are_claimable() {
+		new_test_ext().execute_with(|| {
+			register_contract();
+			FeeSharing::with_target(Some(CONTRACT), || share(30));
+			assert_eq!(FeeSharing::accrued(BENEFICIARY), 3);
+
+			assert_ok!(FeeSharing::claim(RuntimeOrigin::signed(BENEFICIARY)));
+			assert_eq!(Balances::free_balance(BENEFICIARY), 1_003);
+			assert_eq!(Balances::free_balance(FeeSharing::account_id()), 10);
+			assert_noop!(
+				FeeSharing::claim(RuntimeOrigin::signed(BENEFICIARY)),
+				Error::<Test>::NothingToClaim
+			);
+		});
+	}
+
+	#[test]
+	fn v1_endows_the_pallet_account() {
+		new_test_ext().execute_with(|| {
+			let _ = Balances::make_free_balance_be(&FeeSharing::account_id(), 0);
+			StorageVersion::new(0).put::<FeeSharing>();
+
+			migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+			assert_eq!(Balances::free_balance(FeeSharing::account_id()), 10);
+			assert_eq!(FeeSharing::on_chain_storage_version(), 1);
+		});
+	}
+}
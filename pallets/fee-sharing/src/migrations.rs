@@ -0,0 +1,29 @@
+//! Storage migrations for the fee-sharing pallet.
+
+use crate::{Config, Pallet};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+pub mod v1 {
+	use super::*;
+
+	/// Endow the pallet account with the existential deposit, which chains created before
+	/// `GenesisConfig` did it lack.
+	pub struct MigrateToV1<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() < 1 {
+				Pallet::<T>::endow_account();
+				StorageVersion::new(1).put::<Pallet<T>>();
+
+				log::info!(target: "runtime::fee-sharing", "v1 applied successfully");
+				T::DbWeight::get().reads_writes(2, 2)
+			} else {
+				log::warn!(target: "runtime::fee-sharing", "Skipping v1, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
+}
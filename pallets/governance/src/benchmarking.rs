@@ -0,0 +1,163 @@
+//! Benchmarks for the governance pallet.
+
+use super::*;
+use crate::Pallet as Governance;
+
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, LockableCurrency, WithdrawReasons},
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::traits::{Bounded, Zero};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+fn funded<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, 0);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 4u32.into());
+	who
+}
+
+fn proposal<T: Config>() -> Box<<T as Config>::RuntimeCall>
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	let remark = vec![0; T::MaxProposalLength::get().saturating_sub(16) as usize];
+	Box::new(frame_system::Call::<T>::remark { remark }.into())
+}
+
+fn submit_referendum<T: Config>(proposer: &T::AccountId) -> Result<ReferendumIndex, BenchmarkError>
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	let index = ReferendumCount::<T>::get();
+	Governance::<T>::submit(RawOrigin::Signed(proposer.clone()).into(), proposal::<T>())?;
+	Ok(index)
+}
+
+#[benchmarks(where <T as Config>::RuntimeCall: From<frame_system::Call<T>>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn submit() {
+		let caller = funded::<T>("caller", 0);
+		let proposal = proposal::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), proposal);
+
+		assert_eq!(ProposalsOf::<T>::get(&caller), 1);
+	}
+
+	#[benchmark]
+	fn vote() -> Result<(), BenchmarkError> {
+		let index = submit_referendum::<T>(&funded::<T>("proposer", 0))?;
+		let caller = funded::<T>("caller", 0);
+		let balance = T::Currency::free_balance(&caller) / 2u32.into();
+		// Replacing a previous vote is the worst case.
+		let previous = Vote { aye: false, balance, conviction: Conviction::Locked1x };
+		Governance::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, previous)?;
+		let vote = Vote { aye: true, balance, conviction: Conviction::Locked6x };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), index, vote);
+
+		assert_eq!(Votes::<T>::get(index, &caller), Some(vote));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unlock(v: Linear<0, { T::MaxVotes::get() }>) {
+		let caller = funded::<T>("caller", 0);
+		let balance = T::Currency::free_balance(&caller) / 2u32.into();
+		let vote = Vote { aye: true, balance, conviction: Conviction::Locked1x };
+		let now = frame_system::Pallet::<T>::block_number();
+		let locks: Vec<_> = (0..v)
+			.map(|index| {
+				Votes::<T>::insert(index, &caller, vote);
+				(index, now, balance)
+			})
+			.collect();
+		VoteLocks::<T>::insert(&caller, BoundedVec::truncate_from(locks));
+		T::Currency::set_lock(GOVERNANCE_ID, &caller, balance, WithdrawReasons::all());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), caller.clone());
+
+		assert!(VoteLocks::<T>::get(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn cancel() -> Result<(), BenchmarkError> {
+		let index = submit_referendum::<T>(&funded::<T>("proposer", 0))?;
+		let origin =
+			T::CancelOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, index);
+
+		assert!(matches!(Referenda::<T>::get(index), Some(ReferendumInfo::Finished { .. })));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_canceller() {
+		let canceller: T::AccountId = account("canceller", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, Some(canceller.clone()));
+
+		assert_eq!(Canceller::<T>::get(), Some(canceller));
+	}
+
+	#[benchmark]
+	fn close() -> Result<(), BenchmarkError> {
+		let index = submit_referendum::<T>(&funded::<T>("proposer", 0))?;
+		// Passing is the worst case, queueing the proposal for enactment.
+		let issuance = T::Currency::total_issuance();
+		let mut end = BlockNumberFor::<T>::default();
+		Referenda::<T>::mutate(index, |info| {
+			if let Some(ReferendumInfo::Ongoing(status)) = info {
+				status.tally = Tally { ayes: issuance, nays: Zero::zero(), turnout: issuance };
+				end = status.end;
+			}
+		});
+
+		#[block]
+		{
+			Governance::<T>::close(index, end);
+		}
+
+		assert!(matches!(Referenda::<T>::get(index), Some(ReferendumInfo::Approved { .. })));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn enact() -> Result<(), BenchmarkError> {
+		let index = submit_referendum::<T>(&funded::<T>("proposer", 0))?;
+		let Some(ReferendumInfo::Ongoing(status)) = Referenda::<T>::get(index) else {
+			return Err(BenchmarkError::Stop("referendum not ongoing"));
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		Referenda::<T>::insert(
+			index,
+			ReferendumInfo::Approved { end: now, enactment: now, proposal: status.proposal },
+		);
+		Enactments::<T>::insert(0, index);
+		EnactmentBounds::<T>::put((0, 1));
+
+		#[block]
+		{
+			Governance::<T>::enact_next(now);
+		}
+
+		assert_eq!(
+			Referenda::<T>::get(index),
+			Some(ReferendumInfo::Finished { approved: true, end: now })
+		);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Governance, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,1028 @@
+//! Referenda voted with veTAKER.
+//!
+//! Any account may submit a call as a proposal, reserving `SubmissionDeposit`, as long as it has
+//! fewer than `MaxProposalsPerAccount` referenda ongoing and the call weighs at most
+//! `MaxProposalWeight`. Its referendum is voted for `VotingPeriod`, after which it passes if at
+//! least `MinTurnout` of the total issuance voted, and the ayes outweigh the nays and make at least
+//! `MinApproval` of the votes. The deposit is then returned, or slashed if the referendum was
+//! rejected. A passed proposal is dispatched with root origin `EnactmentPeriod` later, one per
+//! block, and `CancelOrigin` may still cancel it until then.
+//!
+//! Votes are conviction-weighted: the balance of a vote stays locked after the referendum ends
+//! for a number of `LockPeriod`s, doubling with every conviction level, and weighs that many
+//! times its balance. A vote without conviction is not locked after the referendum ends and
+//! weighs a tenth of its balance. The locks of the votes of an account overlap, and `unlock`
+//! releases the expired ones.
+//!
+//! Since passed proposals dispatch with root origin, every `EnsureRoot` origin of the runtime is
+//! governed by referenda. Once governance is live, a referendum dispatching `Sudo::remove_key`
+//! removes the sudo key for good.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Saturating},
+	Perbill, RuntimeDebug,
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+/// The index of a referendum.
+pub type ReferendumIndex = u32;
+
+/// How long the balance of a vote stays locked after its referendum ends, and how much it weighs.
+#[derive(
+	Clone, Copy, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub enum Conviction {
+	/// Not locked after the referendum ends, weighs a tenth of the balance.
+	#[default]
+	None,
+	/// Locked for 1 lock period, weighs the balance.
+	Locked1x,
+	/// Locked for 2 lock periods, weighs twice the balance.
+	Locked2x,
+	/// Locked for 4 lock periods, weighs 3 times the balance.
+	Locked3x,
+	/// Locked for 8 lock periods, weighs 4 times the balance.
+	Locked4x,
+	/// Locked for 16 lock periods, weighs 5 times the balance.
+	Locked5x,
+	/// Locked for 32 lock periods, weighs 6 times the balance.
+	Locked6x,
+}
+
+impl Conviction {
+	/// The number of lock periods the balance stays locked after the referendum ends.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			conviction => 1 << (conviction as u8 - 1),
+		}
+	}
+
+	/// The weight of a vote of `balance`.
+	pub fn votes<Balance: AtLeast32BitUnsigned + Copy>(self, balance: Balance) -> Balance {
+		match self {
+			Conviction::None => balance / 10u8.into(),
+			conviction => balance.saturating_mul((conviction as u8).into()),
+		}
+	}
+}
+
+impl TryFrom<u8> for Conviction {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		Ok(match value {
+			0 => Conviction::None,
+			1 => Conviction::Locked1x,
+			2 => Conviction::Locked2x,
+			3 => Conviction::Locked3x,
+			4 => Conviction::Locked4x,
+			5 => Conviction::Locked5x,
+			6 => Conviction::Locked6x,
+			_ => return Err(()),
+		})
+	}
+}
+
+/// A vote in a referendum.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct Vote<Balance> {
+	/// Whether the vote approves the proposal.
+	pub aye: bool,
+	/// The balance voting, locked.
+	pub balance: Balance,
+	/// The conviction of the vote.
+	pub conviction: Conviction,
+}
+
+/// The votes cast in a referendum so far.
+#[derive(
+	Clone, Copy, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug,
+)]
+pub struct Tally<Balance> {
+	/// The weight of the votes approving the proposal.
+	pub ayes: Balance,
+	/// The weight of the votes rejecting the proposal.
+	pub nays: Balance,
+	/// The balance voting, regardless of conviction.
+	pub turnout: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> Tally<Balance> {
+	fn add(&mut self, vote: Vote<Balance>) {
+		let votes = vote.conviction.votes(vote.balance);
+		match vote.aye {
+			true => self.ayes.saturating_accrue(votes),
+			false => self.nays.saturating_accrue(votes),
+		}
+		self.turnout.saturating_accrue(vote.balance);
+	}
+
+	fn remove(&mut self, vote: Vote<Balance>) {
+		let votes = vote.conviction.votes(vote.balance);
+		match vote.aye {
+			true => self.ayes.saturating_reduce(votes),
+			false => self.nays.saturating_reduce(votes),
+		}
+		self.turnout.saturating_reduce(vote.balance);
+	}
+
+	/// Whether the proposal passes: at least `min_turnout` of `electorate` voted, and the ayes
+	/// outweigh the nays and make at least `min_approval` of the votes.
+	pub fn approved(
+		&self,
+		electorate: Balance,
+		min_turnout: Perbill,
+		min_approval: Perbill,
+	) -> bool {
+		self.turnout >= min_turnout.mul_ceil(electorate) &&
+			self.ayes > self.nays &&
+			self.ayes >= min_approval.mul_ceil(self.ayes.saturating_add(self.nays))
+	}
+}
+
+/// A referendum being voted.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ReferendumStatus<AccountId, Balance, BlockNumber, Proposal> {
+	/// The account which submitted the proposal.
+	pub proposer: AccountId,
+	/// The deposit reserved from the proposer.
+	pub deposit: Balance,
+	/// The encoded call dispatched if the referendum passes.
+	pub proposal: Proposal,
+	/// The block the referendum ends at.
+	pub end: BlockNumber,
+	/// The votes cast so far.
+	pub tally: Tally<Balance>,
+}
+
+/// A referendum.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub enum ReferendumInfo<AccountId, Balance, BlockNumber, Proposal> {
+	/// Being voted.
+	Ongoing(ReferendumStatus<AccountId, Balance, BlockNumber, Proposal>),
+	/// Ended at `end`, or cancelled then.
+	Finished { approved: bool, end: BlockNumber },
+	/// Passed at `end`, its proposal enacted at `enactment` unless cancelled before.
+	Approved { end: BlockNumber, enactment: BlockNumber, proposal: Proposal },
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{ReferendumIndex, ReferendumInfo, ReferendumStatus, Tally, Vote, WeightInfo};
+	use codec::{DecodeLimit, Encode};
+	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::{
+			Currency, LockIdentifier, LockableCurrency, OnUnbalanced, ReservableCurrency,
+			WithdrawReasons,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{Dispatchable, Saturating, Zero},
+		Perbill,
+	};
+	use sp_std::boxed::Box;
+
+	/// The identifier of the lock on the balance of votes.
+	pub const GOVERNANCE_ID: LockIdentifier = *b"takergov";
+
+	/// The maximum depth of the calls nested in a proposal.
+	const MAX_PROPOSAL_DEPTH: u32 = 256;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type ProposalOf<T> = BoundedVec<u8, <T as Config>::MaxProposalLength>;
+	pub type VoteOf<T> = Vote<BalanceOf<T>>;
+	pub type ReferendumInfoOf<T> = ReferendumInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		ProposalOf<T>,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching call type, which proposals are.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+		/// The currency votes are cast and deposits reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
+		/// The deposit reserved from the proposer until the referendum ends, and slashed if it is
+		/// rejected or cancelled.
+		#[pallet::constant]
+		type SubmissionDeposit: Get<BalanceOf<Self>>;
+		/// The handler for the slashed deposits.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The maximum number of ongoing referenda an account may have submitted.
+		#[pallet::constant]
+		type MaxProposalsPerAccount: Get<u32>;
+		/// How long a referendum is voted.
+		#[pallet::constant]
+		type VotingPeriod: Get<BlockNumberFor<Self>>;
+		/// The least share of the total issuance which must vote for a referendum to pass.
+		#[pallet::constant]
+		type MinTurnout: Get<Perbill>;
+		/// The least share of the votes, weighted by conviction, which must approve a referendum
+		/// for it to pass.
+		#[pallet::constant]
+		type MinApproval: Get<Perbill>;
+		/// How long after its referendum passes a proposal is enacted.
+		#[pallet::constant]
+		type EnactmentPeriod: Get<BlockNumberFor<Self>>;
+		/// How long a vote with a conviction of 1 stays locked after the referendum ends.
+		#[pallet::constant]
+		type LockPeriod: Get<BlockNumberFor<Self>>;
+		/// The maximum length of an encoded proposal.
+		#[pallet::constant]
+		type MaxProposalLength: Get<u32>;
+		/// The maximum weight of a proposal, which must leave room for the rest of the block it is
+		/// enacted in.
+		#[pallet::constant]
+		type MaxProposalWeight: Get<Weight>;
+		/// The maximum number of referenda ending at the same block.
+		#[pallet::constant]
+		type MaxEnding: Get<u32>;
+		/// The maximum number of votes of an account not unlocked yet.
+		#[pallet::constant]
+		type MaxVotes: Get<u32>;
+		/// The origin which cancels referenda, such as `EnsureCanceller`.
+		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The number of referenda submitted so far, and the index of the next one.
+	#[pallet::storage]
+	#[pallet::getter(fn referendum_count)]
+	pub type ReferendumCount<T: Config> = StorageValue<_, ReferendumIndex, ValueQuery>;
+
+	/// Every referendum.
+	#[pallet::storage]
+	#[pallet::getter(fn referendum_info)]
+	pub type Referenda<T: Config> =
+		StorageMap<_, Twox64Concat, ReferendumIndex, ReferendumInfoOf<T>, OptionQuery>;
+
+	/// The referenda ending at every block.
+	#[pallet::storage]
+	pub type Ending<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<ReferendumIndex, T::MaxEnding>,
+		ValueQuery,
+	>;
+
+	/// The vote of every account in every referendum, until it is unlocked.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_of)]
+	pub type Votes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		VoteOf<T>,
+		OptionQuery,
+	>;
+
+	/// The number of ongoing referenda every account submitted.
+	#[pallet::storage]
+	#[pallet::getter(fn proposals_of)]
+	pub type ProposalsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The referenda which passed, in the order they did, until they are enacted or cancelled.
+	#[pallet::storage]
+	pub type Enactments<T: Config> = StorageMap<_, Twox64Concat, u32, ReferendumIndex, OptionQuery>;
+
+	/// The position in `Enactments` of the next referendum to enact, and of the next to pass.
+	#[pallet::storage]
+	pub type EnactmentBounds<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// The account `EnsureCanceller` accepts, such as a multisig of a technical committee.
+	#[pallet::storage]
+	#[pallet::getter(fn canceller)]
+	pub type Canceller<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The balance every vote of every account locks, and the block it is locked until.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_locks)]
+	pub type VoteLocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(ReferendumIndex, BlockNumberFor<T>, BalanceOf<T>), T::MaxVotes>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A proposal was submitted, and its referendum ends at `end`.
+		Submitted { index: ReferendumIndex, proposer: T::AccountId, end: BlockNumberFor<T> },
+		/// An account voted in a referendum, or changed its vote.
+		Voted { who: T::AccountId, index: ReferendumIndex, vote: VoteOf<T> },
+		/// A referendum passed, and its proposal is enacted at `enactment`.
+		Passed { index: ReferendumIndex, enactment: BlockNumberFor<T> },
+		/// A referendum was rejected.
+		Rejected { index: ReferendumIndex },
+		/// The proposal of a referendum which passed was dispatched.
+		Enacted { index: ReferendumIndex, result: DispatchResult },
+		/// A referendum was cancelled.
+		Cancelled { index: ReferendumIndex },
+		/// The expired locks of the votes of an account were released.
+		Unlocked { who: T::AccountId },
+		/// The account `EnsureCanceller` accepts was set.
+		CancellerSet { canceller: Option<T::AccountId> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The encoded proposal is longer than `MaxProposalLength`.
+		ProposalTooLong,
+		/// The proposal weighs more than `MaxProposalWeight`.
+		ProposalTooHeavy,
+		/// The account already has `MaxProposalsPerAccount` referenda ongoing.
+		TooManyProposals,
+		/// Too many referenda already end at the same block.
+		TooManyEnding,
+		/// The referendum does not exist.
+		UnknownReferendum,
+		/// The referendum has ended.
+		NotOngoing,
+		/// The account has less free balance than it votes.
+		InsufficientFunds,
+		/// The account has too many votes not unlocked yet.
+		TooManyVotes,
+		/// The proposal no longer decodes as a call.
+		UndecodableProposal,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for index in Ending::<T>::take(now) {
+				weight.saturating_accrue(Self::close(index, now));
+			}
+			weight.saturating_add(Self::enact_next(now))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit `proposal` to a referendum, reserving `SubmissionDeposit` from the origin until
+		/// it ends.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit())]
+		pub fn submit(
+			origin: OriginFor<T>,
+			proposal: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				proposal.get_dispatch_info().weight.all_lte(T::MaxProposalWeight::get()),
+				Error::<T>::ProposalTooHeavy
+			);
+			ensure!(
+				ProposalsOf::<T>::get(&who) < T::MaxProposalsPerAccount::get(),
+				Error::<T>::TooManyProposals
+			);
+			let proposal: ProposalOf<T> =
+				proposal.encode().try_into().map_err(|_| Error::<T>::ProposalTooLong)?;
+
+			let index = ReferendumCount::<T>::get();
+			let now = frame_system::Pallet::<T>::block_number();
+			let end = now.saturating_add(T::VotingPeriod::get());
+			Ending::<T>::try_append(end, index).map_err(|_| Error::<T>::TooManyEnding)?;
+			let deposit = T::SubmissionDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			ProposalsOf::<T>::mutate(&who, |proposals| proposals.saturating_inc());
+
+			let status = ReferendumStatus {
+				proposer: who.clone(),
+				deposit,
+				proposal,
+				end,
+				tally: Tally::default(),
+			};
+			Referenda::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			ReferendumCount::<T>::put(index.saturating_add(1));
+			Self::deposit_event(Event::Submitted { index, proposer: who, end });
+			Ok(())
+		}
+
+		/// Vote in an ongoing referendum, replacing the previous vote of the origin in it. The
+		/// balance of the vote is locked until the referendum ends, and for the lock periods of
+		/// its conviction after.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(
+			origin: OriginFor<T>,
+			index: ReferendumIndex,
+			vote: VoteOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Referenda::<T>::get(index).ok_or(Error::<T>::UnknownReferendum)?;
+			let ReferendumInfo::Ongoing(status) = &mut info else {
+				return Err(Error::<T>::NotOngoing.into());
+			};
+			ensure!(
+				vote.balance <= T::Currency::free_balance(&who),
+				Error::<T>::InsufficientFunds
+			);
+
+			if let Some(previous) = Votes::<T>::get(index, &who) {
+				status.tally.remove(previous);
+			}
+			status.tally.add(vote);
+			let lock_period =
+				T::LockPeriod::get().saturating_mul(vote.conviction.lock_periods().into());
+			let locked_until = status.end.saturating_add(lock_period);
+			VoteLocks::<T>::try_mutate(&who, |locks| {
+				locks.retain(|(voted, ..)| *voted != index);
+				locks.try_push((index, locked_until, vote.balance))
+			})
+			.map_err(|_| Error::<T>::TooManyVotes)?;
+			Self::update_lock(&who);
+
+			Votes::<T>::insert(index, &who, vote);
+			Referenda::<T>::insert(index, info);
+			Self::deposit_event(Event::Voted { who, index, vote });
+			Ok(())
+		}
+
+		/// Release the locks of the votes of `target` which expired.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::unlock(T::MaxVotes::get()))]
+		pub fn unlock(origin: OriginFor<T>, target: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			VoteLocks::<T>::mutate(&target, |locks| {
+				locks.retain(|(index, locked_until, _)| {
+					let expired = *locked_until <= now;
+					if expired {
+						Votes::<T>::remove(index, &target);
+					}
+					!expired
+				})
+			});
+			Self::update_lock(&target);
+			Self::deposit_event(Event::Unlocked { who: target });
+			Ok(())
+		}
+
+		/// Cancel an ongoing referendum, slashing the deposit of its proposer, or a passed one
+		/// not enacted yet. The votes cast stay locked as if it ended.
+		///
+		/// The dispatch origin for this call must be `CancelOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let info = Referenda::<T>::get(index).ok_or(Error::<T>::UnknownReferendum)?;
+			let end = match info {
+				ReferendumInfo::Ongoing(status) => {
+					Ending::<T>::mutate(status.end, |ending| {
+						ending.retain(|ending| *ending != index)
+					});
+					ProposalsOf::<T>::mutate(&status.proposer, |proposals| {
+						proposals.saturating_dec()
+					});
+					Self::slash_deposit(&status.proposer, status.deposit);
+					frame_system::Pallet::<T>::block_number()
+				},
+				// Skipped once its turn to be enacted comes.
+				ReferendumInfo::Approved { end, .. } => end,
+				ReferendumInfo::Finished { .. } => return Err(Error::<T>::NotOngoing.into()),
+			};
+
+			Referenda::<T>::insert(index, ReferendumInfo::Finished { approved: false, end });
+			Self::deposit_event(Event::Cancelled { index });
+			Ok(())
+		}
+
+		/// Set the account `EnsureCanceller` accepts, or unset it.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_canceller())]
+		pub fn set_canceller(
+			origin: OriginFor<T>,
+			canceller: Option<T::AccountId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Canceller::<T>::set(canceller.clone());
+			Self::deposit_event(Event::CancellerSet { canceller });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ends a referendum, queueing its proposal for enactment if it passed.
+		pub(crate) fn close(index: ReferendumIndex, now: BlockNumberFor<T>) -> Weight {
+			let weight = T::WeightInfo::close();
+			let Some(ReferendumInfo::Ongoing(status)) = Referenda::<T>::get(index) else {
+				return weight;
+			};
+
+			ProposalsOf::<T>::mutate(&status.proposer, |proposals| proposals.saturating_dec());
+			let approved = status.tally.approved(
+				T::Currency::total_issuance(),
+				T::MinTurnout::get(),
+				T::MinApproval::get(),
+			);
+			if !approved {
+				Self::slash_deposit(&status.proposer, status.deposit);
+				Referenda::<T>::insert(index, ReferendumInfo::Finished { approved, end: now });
+				Self::deposit_event(Event::Rejected { index });
+				return weight;
+			}
+
+			T::Currency::unreserve(&status.proposer, status.deposit);
+			let enactment = now.saturating_add(T::EnactmentPeriod::get());
+			let proposal = status.proposal;
+			Referenda::<T>::insert(
+				index,
+				ReferendumInfo::Approved { end: now, enactment, proposal },
+			);
+			EnactmentBounds::<T>::mutate(|(_, next)| {
+				Enactments::<T>::insert(*next, index);
+				next.saturating_inc();
+			});
+			Self::deposit_event(Event::Passed { index, enactment });
+			weight
+		}
+
+		/// Dispatches with root origin the proposal of the next referendum which passed, if its
+		/// enactment is due, or skips it if it was cancelled.
+		pub(crate) fn enact_next(now: BlockNumberFor<T>) -> Weight {
+			let (first, next) = EnactmentBounds::<T>::get();
+			let Some(index) = (first < next).then(|| Enactments::<T>::get(first)).flatten() else {
+				return T::DbWeight::get().reads(1);
+			};
+
+			let mut weight = T::WeightInfo::enact();
+			let info = Referenda::<T>::get(index);
+			if let Some(ReferendumInfo::Approved { end, enactment, proposal }) = info {
+				if enactment > now {
+					return weight;
+				}
+
+				let result = match <T as Config>::RuntimeCall::decode_all_with_depth_limit(
+					MAX_PROPOSAL_DEPTH,
+					&mut &proposal[..],
+				) {
+					Ok(call) => {
+						weight.saturating_accrue(call.get_dispatch_info().weight);
+						call.dispatch(frame_system::RawOrigin::Root.into())
+							.map(|_| ())
+							.map_err(|e| e.error)
+					},
+					Err(_) => Err(Error::<T>::UndecodableProposal.into()),
+				};
+				Referenda::<T>::insert(index, ReferendumInfo::Finished { approved: true, end });
+				Self::deposit_event(Event::Enacted { index, result });
+			}
+			Enactments::<T>::remove(first);
+			EnactmentBounds::<T>::put((first.saturating_add(1), next));
+			weight
+		}
+
+		/// Slashes the deposit of a proposer, handing it to `Slash`.
+		fn slash_deposit(proposer: &T::AccountId, deposit: BalanceOf<T>) {
+			let (slashed, _) = T::Currency::slash_reserved(proposer, deposit);
+			T::Slash::on_unbalanced(slashed);
+		}
+
+		/// Locks the largest balance the votes of `who` lock.
+		fn update_lock(who: &T::AccountId) {
+			let locked = VoteLocks::<T>::get(who)
+				.iter()
+				.map(|(_, _, balance)| *balance)
+				.max()
+				.unwrap_or_else(Zero::zero);
+			if locked.is_zero() {
+				T::Currency::remove_lock(GOVERNANCE_ID, who);
+			} else {
+				T::Currency::set_lock(GOVERNANCE_ID, who, locked, WithdrawReasons::all());
+			}
+		}
+	}
+
+	/// Accepts a signed origin from `Canceller`.
+	pub struct EnsureCanceller<T>(PhantomData<T>);
+
+	impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureCanceller<T> {
+		type Success = T::AccountId;
+
+		fn try_origin(origin: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+			origin.into().and_then(|origin| match origin {
+				frame_system::RawOrigin::Signed(who)
+					if Canceller::<T>::get().as_ref() == Some(&who) =>
+					Ok(who),
+				origin => Err(T::RuntimeOrigin::from(origin)),
+			})
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+			Canceller::<T>::get()
+				.map(|who| frame_system::RawOrigin::Signed(who).into())
+				.ok_or(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_governance;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl, parameter_types,
+		traits::{ConstU32, ConstU64, Currency, EitherOfDiverse, Hooks, ReservableCurrency},
+		weights::Weight,
+	};
+	use sp_runtime::{BuildStorage, DispatchError};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			Governance: pallet_governance,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	parameter_types! {
+		pub const MinTurnout: Perbill = Perbill::from_percent(10);
+		pub const MinApproval: Perbill = Perbill::from_percent(60);
+		pub static MaxProposalWeight: Weight = Weight::MAX;
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeCall = RuntimeCall;
+		type Currency = Balances;
+		type SubmissionDeposit = ConstU64<DEPOSIT>;
+		type Slash = ();
+		type MaxProposalsPerAccount = ConstU32<1>;
+		type VotingPeriod = ConstU64<VOTING_PERIOD>;
+		type MinTurnout = MinTurnout;
+		type MinApproval = MinApproval;
+		type EnactmentPeriod = ConstU64<ENACTMENT_PERIOD>;
+		type LockPeriod = ConstU64<LOCK_PERIOD>;
+		type MaxProposalLength = ConstU32<1024>;
+		type MaxProposalWeight = MaxProposalWeight;
+		type MaxEnding = ConstU32<4>;
+		type MaxVotes = ConstU32<4>;
+		type CancelOrigin = EitherOfDiverse<
+			frame_system::EnsureRoot<u64>,
+			pallet_governance::EnsureCanceller<Test>,
+		>;
+		type WeightInfo = ();
+	}
+
+	const ALICE: u64 = 1;
+	const BOB: u64 = 2;
+	const CHARLIE: u64 = 3;
+	const DAVE: u64 = 4;
+	const EVE: u64 = 5;
+
+	const DEPOSIT: u64 = 100;
+	const VOTING_PERIOD: u64 = 10;
+	const ENACTMENT_PERIOD: u64 = 3;
+	const LOCK_PERIOD: u64 = 5;
+	/// The block the referenda submitted at block 1 end at.
+	const END: u64 = 1 + VOTING_PERIOD;
+
+	pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(ALICE, 1_000), (BOB, 1_000), (CHARLIE, 1_000), (DAVE, 7_000)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn run_to(n: u64) {
+		while System::block_number() < n {
+			System::set_block_number(System::block_number() + 1);
+			Governance::on_initialize(System::block_number());
+		}
+	}
+
+	/// A proposal setting the balance of `EVE`.
+	fn proposal(balance: u64) -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::Balances(pallet_balances::Call::force_set_balance {
+			who: EVE,
+			new_free: balance,
+		}))
+	}
+
+	fn vote(aye: bool, balance: u64, conviction: Conviction) -> Vote<u64> {
+		Vote { aye, balance, conviction }
+	}
+
+	#[test]
+	fn convictions_weigh_and_lock_votes() {
+		assert_eq!(Conviction::None.votes(1_000u64), 100);
+		assert_eq!(Conviction::None.lock_periods(), 0);
+		assert_eq!(Conviction::Locked1x.votes(1_000u64), 1_000);
+		assert_eq!(Conviction::Locked1x.lock_periods(), 1);
+		assert_eq!(Conviction::Locked3x.votes(1_000u64), 3_000);
+		assert_eq!(Conviction::Locked3x.lock_periods(), 4);
+		assert_eq!(Conviction::Locked6x.votes(1_000u64), 6_000);
+		assert_eq!(Conviction::Locked6x.lock_periods(), 32);
+		assert_eq!(Conviction::try_from(7), Err(()));
+	}
+
+	#[test]
+	fn tally_requires_turnout_and_approval() {
+		let approved = |tally: &Tally<u64>| {
+			tally.approved(10_000, Perbill::from_percent(10), Perbill::from_percent(60))
+		};
+
+		let mut tally = Tally::default();
+		tally.add(vote(true, 600, Conviction::Locked1x));
+		tally.add(vote(false, 400, Conviction::Locked1x));
+		assert_eq!(tally, Tally { ayes: 600, nays: 400, turnout: 1_000 });
+		assert!(approved(&tally));
+
+		// Below the turnout.
+		tally.remove(vote(false, 400, Conviction::Locked1x));
+		tally.add(vote(false, 399, Conviction::Locked1x));
+		assert!(!approved(&tally));
+
+		// Below the approval, though the ayes outweigh the nays.
+		tally.add(vote(false, 1, Conviction::Locked1x));
+		tally.add(vote(false, 100, Conviction::None));
+		assert_eq!(tally, Tally { ayes: 600, nays: 410, turnout: 1_100 });
+		assert!(!approved(&tally));
+
+		// Conviction weighs, but turnout counts the balance.
+		tally.add(vote(true, 10, Conviction::Locked3x));
+		assert_eq!(tally, Tally { ayes: 630, nays: 410, turnout: 1_110 });
+		assert!(approved(&tally));
+
+		// The ayes must outweigh the nays whatever the approval.
+		let tally = Tally { ayes: 500, nays: 500, turnout: 1_000 };
+		assert!(!tally.approved(10_000, Perbill::zero(), Perbill::zero()));
+	}
+
+	#[test]
+	fn passed_proposals_are_enacted_after_the_enactment_period() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_eq!(Balances::reserved_balance(ALICE), DEPOSIT);
+			assert_eq!(Governance::proposals_of(ALICE), 1);
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(DAVE),
+				0,
+				vote(true, 1_000, Conviction::Locked1x)
+			));
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(BOB),
+				0,
+				vote(false, 500, Conviction::Locked1x)
+			));
+
+			run_to(END);
+			let enactment = END + ENACTMENT_PERIOD;
+			System::assert_last_event(Event::Passed { index: 0, enactment }.into());
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::free_balance(ALICE), 1_000);
+			assert_eq!(Governance::proposals_of(ALICE), 0);
+			assert!(matches!(
+				Governance::referendum_info(0),
+				Some(ReferendumInfo::Approved { end: END, enactment: e, .. }) if e == enactment
+			));
+
+			run_to(enactment - 1);
+			assert_eq!(Balances::free_balance(EVE), 0);
+
+			run_to(enactment);
+			assert_eq!(Balances::free_balance(EVE), 42);
+			System::assert_last_event(Event::Enacted { index: 0, result: Ok(()) }.into());
+			assert_eq!(
+				Governance::referendum_info(0),
+				Some(ReferendumInfo::Finished { approved: true, end: END })
+			);
+			assert_eq!(EnactmentBounds::<Test>::get(), (1, 1));
+		});
+	}
+
+	#[test]
+	fn one_proposal_is_enacted_per_block() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(BOB), proposal(43)));
+			for index in 0..2 {
+				assert_ok!(Governance::vote(
+					RuntimeOrigin::signed(DAVE),
+					index,
+					vote(true, 1_000, Conviction::Locked1x)
+				));
+			}
+
+			run_to(END + ENACTMENT_PERIOD);
+			assert_eq!(Balances::free_balance(EVE), 42);
+			run_to(END + ENACTMENT_PERIOD + 1);
+			assert_eq!(Balances::free_balance(EVE), 43);
+			assert_eq!(EnactmentBounds::<Test>::get(), (2, 2));
+		});
+	}
+
+	#[test]
+	fn rejected_referenda_slash_the_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			// Below the turnout of a tenth of the issuance.
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(BOB),
+				0,
+				vote(true, 999, Conviction::Locked6x)
+			));
+
+			run_to(END);
+			System::assert_last_event(Event::Rejected { index: 0 }.into());
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::free_balance(ALICE), 1_000 - DEPOSIT);
+			assert_eq!(Balances::total_issuance(), 10_000 - DEPOSIT);
+			assert_eq!(Governance::proposals_of(ALICE), 0);
+			assert_eq!(
+				Governance::referendum_info(0),
+				Some(ReferendumInfo::Finished { approved: false, end: END })
+			);
+
+			run_to(END + ENACTMENT_PERIOD);
+			assert_eq!(Balances::free_balance(EVE), 0);
+		});
+	}
+
+	#[test]
+	fn submissions_are_limited() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_noop!(
+				Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)),
+				Error::<Test>::TooManyProposals
+			);
+
+			MaxProposalWeight::set(Weight::zero());
+			assert_noop!(
+				Governance::submit(RuntimeOrigin::signed(BOB), proposal(42)),
+				Error::<Test>::ProposalTooHeavy
+			);
+			MaxProposalWeight::set(Weight::MAX);
+
+			run_to(END);
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+		});
+	}
+
+	#[test]
+	fn the_canceller_cancels_ongoing_referenda() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_noop!(
+				Governance::cancel(RuntimeOrigin::signed(CHARLIE), 0),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Governance::set_canceller(RuntimeOrigin::signed(CHARLIE), Some(CHARLIE)),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(Governance::set_canceller(RuntimeOrigin::root(), Some(CHARLIE)));
+			System::assert_last_event(Event::CancellerSet { canceller: Some(CHARLIE) }.into());
+			assert_ok!(Governance::cancel(RuntimeOrigin::signed(CHARLIE), 0));
+			System::assert_last_event(Event::Cancelled { index: 0 }.into());
+			assert_eq!(Balances::free_balance(ALICE), 1_000 - DEPOSIT);
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Governance::proposals_of(ALICE), 0);
+			assert!(Ending::<Test>::get(END).is_empty());
+			assert_eq!(
+				Governance::referendum_info(0),
+				Some(ReferendumInfo::Finished { approved: false, end: 1 })
+			);
+			assert_noop!(
+				Governance::cancel(RuntimeOrigin::signed(CHARLIE), 0),
+				Error::<Test>::NotOngoing
+			);
+		});
+	}
+
+	#[test]
+	fn passed_referenda_are_cancelled_before_enactment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(DAVE),
+				0,
+				vote(true, 1_000, Conviction::Locked1x)
+			));
+
+			run_to(END);
+			assert_ok!(Governance::cancel(RuntimeOrigin::root(), 0));
+			// The deposit of a passed referendum is already returned.
+			assert_eq!(Balances::free_balance(ALICE), 1_000);
+			assert_eq!(
+				Governance::referendum_info(0),
+				Some(ReferendumInfo::Finished { approved: false, end: END })
+			);
+
+			run_to(END + ENACTMENT_PERIOD);
+			assert_eq!(Balances::free_balance(EVE), 0);
+			assert_eq!(EnactmentBounds::<Test>::get(), (1, 1));
+			assert_noop!(Governance::cancel(RuntimeOrigin::root(), 0), Error::<Test>::NotOngoing);
+		});
+	}
+
+	#[test]
+	fn votes_stay_locked_for_their_conviction() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Governance::submit(RuntimeOrigin::signed(ALICE), proposal(42)));
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(DAVE),
+				0,
+				vote(true, 1_000, Conviction::Locked2x)
+			));
+			assert_ok!(Governance::vote(
+				RuntimeOrigin::signed(BOB),
+				0,
+				vote(false, 500, Conviction::None)
+			));
+			assert_noop!(
+				Governance::vote(
+					RuntimeOrigin::signed(BOB),
+					0,
+					vote(false, 1_001, Conviction::None)
+				),
+				Error::<Test>::InsufficientFunds
+			);
+			assert_noop!(
+				Balances::reserve(&DAVE, 6_001),
+				pallet_balances::Error::<Test>::LiquidityRestrictions
+			);
+
+			run_to(END);
+			assert_ok!(Governance::unlock(RuntimeOrigin::signed(ALICE), BOB));
+			System::assert_last_event(Event::Unlocked { who: BOB }.into());
+			assert!(Governance::vote_locks(BOB).is_empty());
+			assert_eq!(Governance::vote_of(0, BOB), None);
+			assert!(pallet_balances::Locks::<Test>::get(BOB).is_empty());
+
+			// Locked for 2 lock periods after the referendum ends.
+			run_to(END + 2 * LOCK_PERIOD - 1);
+			assert_ok!(Governance::unlock(RuntimeOrigin::signed(ALICE), DAVE));
+			assert_eq!(pallet_balances::Locks::<Test>::get(DAVE)[0].amount, 1_000);
+			assert!(Governance::vote_of(0, DAVE).is_some());
+
+			run_to(END + 2 * LOCK_PERIOD);
+			assert_ok!(Governance::unlock(RuntimeOrigin::signed(ALICE), DAVE));
+			assert!(pallet_balances::Locks::<Test>::get(DAVE).is_empty());
+			assert_eq!(Governance::vote_of(0, DAVE), None);
+		});
+	}
+}
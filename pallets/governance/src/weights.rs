@@ -0,0 +1,161 @@
+//! Weights for pallet_governance.
+//!
+//! Estimated from the storage every call accesses, until they are generated from
+//! `benchmarking.rs` with `benchmark pallet --pallet=pallet_governance` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_governance.
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn vote() -> Weight;
+	fn unlock(v: u32, ) -> Weight;
+	fn cancel() -> Weight;
+	fn set_canceller() -> Weight;
+	fn close() -> Weight;
+	fn enact() -> Weight;
+}
+
+/// Weights for pallet_governance using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: Governance ReferendumCount (r:1 w:1)
+	/// Storage: Governance Ending (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Governance Referenda (r:0 w:1)
+	fn submit() -> Weight {
+		Weight::from_parts(60_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance Votes (r:1 w:1)
+	/// Storage: Governance VoteLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn vote() -> Weight {
+		Weight::from_parts(70_000_000, 24_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Governance VoteLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Governance Votes (r:0 w:v)
+	/// The range of component `v` is `[0, 64]`.
+	fn unlock(v: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(v.into())))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance Ending (r:1 w:1)
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel() -> Weight {
+		Weight::from_parts(50_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Governance Canceller (r:0 w:1)
+	fn set_canceller() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Governance EnactmentBounds (r:1 w:1)
+	/// Storage: Governance Enactments (r:0 w:1)
+	fn close() -> Weight {
+		Weight::from_parts(50_000_000, 40_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Governance EnactmentBounds (r:1 w:1)
+	/// Storage: Governance Enactments (r:1 w:1)
+	/// Storage: Governance Referenda (r:1 w:1)
+	fn enact() -> Weight {
+		Weight::from_parts(30_000_000, 24_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: Governance ReferendumCount (r:1 w:1)
+	/// Storage: Governance Ending (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Governance Referenda (r:0 w:1)
+	fn submit() -> Weight {
+		Weight::from_parts(60_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance Votes (r:1 w:1)
+	/// Storage: Governance VoteLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn vote() -> Weight {
+		Weight::from_parts(70_000_000, 24_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Governance VoteLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Governance Votes (r:0 w:v)
+	/// The range of component `v` is `[0, 64]`.
+	fn unlock(v: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(v.into())))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance Ending (r:1 w:1)
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel() -> Weight {
+		Weight::from_parts(50_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Governance Canceller (r:0 w:1)
+	fn set_canceller() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Governance Referenda (r:1 w:1)
+	/// Storage: Governance ProposalsOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Governance EnactmentBounds (r:1 w:1)
+	/// Storage: Governance Enactments (r:0 w:1)
+	fn close() -> Weight {
+		Weight::from_parts(50_000_000, 40_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Governance EnactmentBounds (r:1 w:1)
+	/// Storage: Governance Enactments (r:1 w:1)
+	/// Storage: Governance Referenda (r:1 w:1)
+	fn enact() -> Weight {
+		Weight::from_parts(30_000_000, 24_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Batch Interface
+* @dev This interface runs several calls in one transaction, each with the caller of the batch as
+* msg.sender, e.g. approving veTAKER and bonding it at once.
+* Missing values, call data and gas limits default to 0, empty and all the remaining gas. A gas
+* limit of 0 forwards all the remaining gas.
+* Address :  0x0000000000000000000000000000000000000451
+*/
+
+interface Batch {
+    /// Run every subcall, skipping the failing ones.
+    function batchSome(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// Run the subcalls up to the first failing one, keeping the effects of the previous ones.
+    function batchSomeUntilFailure(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// Run every subcall, reverting the whole batch if one fails.
+    function batchAll(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    event SubcallSucceeded(uint256 index);
+    event SubcallFailed(uint256 index);
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Call Permit Interface
+* @dev This interface dispatches calls signed off-chain with EIP-712 permits, so that a relayer
+* pays the gas on behalf of the signer. The call runs with the signer as msg.sender.
+* The permit is signed over the domain
+*   EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+* with name "Call Permit Precompile" and version "1", and the message
+*   CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit,uint256 nonce,uint256 deadline)
+* The deadline is a unix timestamp in seconds.
+* Address :  0x0000000000000000000000000000000000000453
+*/
+
+interface CallPermit {
+    /// Dispatch the call permitted by `from`, returning its output. Reverts if the call fails.
+    function dispatch(
+        address from,
+        address to,
+        uint256 value,
+        bytes memory data,
+        uint64 gaslimit,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bytes memory output);
+
+    /// The nonce the next permit of `owner` must be signed with.
+    function nonces(address owner) external view returns (uint256);
+
+    /// The EIP-712 domain separator of the permits.
+    function domainSeparator() external view returns (bytes32);
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Fee Sharing Interface
+* @dev This interface wraps the developer gas rebates: the deployer of a contract registers it
+* with a beneficiary, to which a share of the gas fees paid to call the contract accrues.
+* The beneficiary claims its rebates with `claim`.
+* Address :  0x0000000000000000000000000000000000000454
+*/
+
+interface FeeSharing {
+    function beneficiaryOf(address contractAddress) external view returns (bool registered, address beneficiary);
+    function accrued(address beneficiary) external view returns (uint256);
+    /// The percentage of the gas fees accrued to beneficiaries.
+    function developerShare() external view returns (uint8);
+    function register(address contractAddress, address beneficiary) external;
+    function unregister(address contractAddress) external;
+    function claim() external;
+}
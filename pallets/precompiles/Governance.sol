@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Governance Interface
+* @dev This interface wraps the referenda voted with veTAKER: any account submits a SCALE encoded
+* runtime call as a proposal, dispatched with root origin some time after its referendum passes.
+* Votes lock their balance until the referendum ends, and for longer the higher their conviction.
+* Address :  0x0000000000000000000000000000000000000455
+*/
+
+interface Governance {
+    function referendumCount() external view returns (uint32);
+    /// The state of a referendum: 0 unknown, 1 ongoing, 2 approved and enacted, 3 rejected or
+    /// cancelled, 4 approved and awaiting enactment, the block it ends or ended at, or is enacted
+    /// at if it awaits enactment, and the ayes, nays and turnout of an ongoing one.
+    function referendumInfo(uint32 index) external view returns (uint8 state, uint32 end, uint256 ayes, uint256 nays, uint256 turnout);
+    function voteOf(uint32 index, address voter) external view returns (bool voted, bool aye, uint256 balance, uint8 conviction);
+    function submit(bytes memory proposal) external;
+    /// Convictions go from 0, weighing a tenth of the balance and not locked after the
+    /// referendum ends, to 6, weighing 6 times the balance and locked for 32 lock periods.
+    function vote(uint32 index, bool aye, uint256 balance, uint8 conviction) external;
+    /// Releases the expired locks of the votes of `target`.
+    function unlock(address target) external;
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Pallet Multisig Interface
+* @dev This interface operates the multisig accounts of pallet_multisig from EVM accounts.
+* Calls are SCALE encoded runtime calls. Signatories need not be sorted, and calls taking more
+* than the MaxSignatories of the runtime revert.
+* Timepoints are the (height, index) of the extrinsic opening an operation, with height 0 for none.
+* Address :  0x0000000000000000000000000000000000000452
+*/
+
+interface Multisig {
+    function multisigAccount(address[] memory signatories, uint16 threshold) external view returns (address);
+    function pending(address multisig, bytes32 callHash) external view returns (
+        bool exists,
+        uint32 height,
+        uint32 index,
+        uint256 deposit,
+        address depositor,
+        address[] memory approvals
+    );
+    function deposit(uint16 threshold) external view returns (uint256);
+    function asMultiThreshold1(address[] memory otherSignatories, bytes memory call) external;
+    function asMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        uint32 height,
+        uint32 index,
+        bytes memory call
+    ) external;
+    function approveAsMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        uint32 height,
+        uint32 index,
+        bytes32 callHash
+    ) external;
+    function cancelAsMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        uint32 height,
+        uint32 index,
+        bytes32 callHash
+    ) external;
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+/**
+* @title Pallet Provider Interface
+* @dev This interface wraps the compute provider registry: registering a provider and its
+* devices, pledging veTAKER against them, and claiming rewards.
+* Address :  0x0000000000000000000000000000000000000450
+*/
+
+interface Provider {
+    function providerOf(address owner) external view returns (bool exists, uint256 pid);
+    function provider(uint256 pid) external view returns (
+        address owner,
+        uint256 capPledge,
+        uint256 totalPledge,
+        uint256 devicesNum,
+        uint256 totalPunishment,
+        uint256 totalRewards,
+        uint256 unpaidRewards
+    );
+    function hasDevice(uint256 pid, bytes32 device) external view returns (bool);
+    function register() external;
+    function deregister() external;
+    function pledge(uint256 amount) external;
+    function unpledge(uint256 amount) external;
+    function addDevice(bytes32 device) external;
+    function removeDevice(bytes32 device) external;
+    function claimRewards() external;
+}
@@ -5,6 +5,13 @@ pragma solidity >=0.8.0;
 * Address :  0x000000000000000000000000000000000000044f
 */
 
+/**
+* Rewards destinations are passed to `bondAndNominate`, `bondAndValidate` and `setPayee`, and
+* logged by `PayeeSet`, as 0 staked, 1 stash or 4 auto compounding.
+* `payee` returns them as addresses: address(1) staked, address(2) stash, address(3) controller,
+* address(4) auto compounding, address(0) none, or the account the rewards are paid into.
+* Auto compounding has the same code in both.
+*/
 interface Staking {
     function validatorCount() external view returns (uint256);
     function stashAccount(address account) external view returns (address);
@@ -17,11 +24,12 @@ interface Staking {
     function eraValidatorReward(uint256 era, address validator) external view returns (uint256);
     function eraNominatorReward(uint256 era, address nominator) external view returns (uint256);
     function erasTotalStake(uint256 era) external view returns (uint256);
+    function delegation(address delegate) external view returns (address, uint8[] memory, uint256);
 
     /** @dev Try nominate some valitors
     * Selector:
     * @param bond_value the target amount balance want to staking
-    * @param payment_destination the target rewards destination
+    * @param payment_destination the rewards destination: 0 staked, 1 stash, 4 auto compounding
     * @param validators the target validators want to nominate
     */
     function bondAndNominate(uint256 bond_value, uint256 payment_destination, address[] memory validators) external;
@@ -29,7 +37,7 @@ interface Staking {
     /** @dev Try been validator
     * Selector:
     * @param bond_value the target amount balance want to staking
-    * @param payment_destination the target rewards destination
+    * @param payment_destination the rewards destination: 0 staked, 1 stash, 4 auto compounding
     * @param commission the proportion validator get
     * @param can_nominated if the validator want to be nominated
     * @param session_keys the combine pubkey for the velidator
@@ -65,11 +73,21 @@ interface Staking {
 
     /** @dev Try been validator
     * Selector:
-    * @param payment_destination the index of enum RewardDestination
+    * @param payment_destination the rewards destination: 0 staked, 1 stash, 4 auto compounding,
+    * reverting otherwise
     */
     function setPayee(uint256 payment_destination) external;
 
 
+    /** @dev Compound rewards automatically
+    * Selector:
+    * @param payment_destination must be 4, auto compounding
+    * @param threshold unpaid rewards are paid out at the end of an era once they reach it
+    * @param stake the percentage of each payout added to the bond
+    */
+    function setPayee(uint256 payment_destination, uint256 threshold, uint256 stake) external;
+
+
     /** @dev Try been validator
     * Selector:
     * @param num_slashing_spans the determine the weight of the transaction (more storage items to remove means more weight, should get from SlashingSpans storage
@@ -112,4 +130,31 @@ interface Staking {
     function bondExtraAndNominate(uint256 bond_value, address[] memory validators) external;
 
     function bondExtraAndValidate(uint256 bond_value, uint256 commission, bool can_nominated, bytes memory session_keys, bytes memory proof) external;
-}
\ No newline at end of file
+
+    /** @dev Allow another account to operate on behalf of the caller stash
+    * Selector:
+    * @param delegate the account receiving the permissions
+    * @param operations the allowed operations, at most 3: 0 validate, 1 session keys and chill, 2 payout
+    * @param expiry the block at which the delegation expires, 0 for never
+    */
+    function addDelegation(address delegate, uint8[] memory operations, uint256 expiry) external;
+
+    /** @dev Revoke a delegation granted by the caller stash
+    * Selector:
+    * @param delegate the account to revoke
+    */
+    function removeDelegation(address delegate) external;
+
+    /// The bond of `staker` grew by `amount`.
+    event Bonded(address indexed staker, uint256 amount);
+    /// `amount` of the bond of `staker` started unbonding.
+    event Unbonded(address indexed staker, uint256 amount);
+    /// `nominator` nominated `validators`.
+    event Nominated(address indexed nominator, address[] validators);
+    /// The rewards of `validator` and its nominators for `era` were paid out.
+    event RewardPaid(address indexed validator, uint256 era);
+    /// `staker` stopped validating or nominating.
+    event Chilled(address indexed staker);
+    /// `staker` set its rewards destination: 0 staked, 1 stash, 4 auto compounding.
+    event PayeeSet(address indexed staker, uint8 payee);
+}
@@ -0,0 +1,352 @@
+use fp_evm::{Context, ExitError, ExitReason, Log, PrecompileFailure, Transfer};
+use frame_support::traits::ConstU32;
+use precompile_utils::{evm::costs::call_cost, prelude::*};
+use sp_core::{H160, U256};
+use sp_std::{iter::repeat, marker::PhantomData, vec, vec::Vec};
+
+/// How a batch handles a failing subcall.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+	/// Skip the failing subcall and carry on.
+	BatchSome,
+	/// Stop at the failing subcall, keeping the effects of the previous ones.
+	BatchSomeUntilFailure,
+	/// Revert the whole batch.
+	BatchAll,
+}
+
+pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = keccak256!("SubcallSucceeded(uint256)");
+pub const LOG_SUBCALL_FAILED: [u8; 32] = keccak256!("SubcallFailed(uint256)");
+
+/// The maximum number of subcalls in a batch.
+pub const ARRAY_LIMIT: u32 = 2u32.pow(9);
+/// The maximum size of the call data of a subcall.
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetArrayLimit = ConstU32<ARRAY_LIMIT>;
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+fn log_subcall_succeeded(address: impl Into<H160>, index: usize) -> Log {
+	log1(address, LOG_SUBCALL_SUCCEEDED, solidity::encode_event_data(U256::from(index)))
+}
+
+fn log_subcall_failed(address: impl Into<H160>, index: usize) -> Log {
+	log1(address, LOG_SUBCALL_FAILED, solidity::encode_event_data(U256::from(index)))
+}
+
+/// Runs several EVM calls in one transaction, each with the caller of the batch as
+/// `msg.sender`.
+pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BatchPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	#[precompile::public("batchSome(address[],uint256[],bytes[],uint64[])")]
+	#[precompile::public("batch_some(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::batch(Mode::BatchSome, handle, to, value, call_data, gas_limit)
+	}
+
+	#[precompile::public("batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])")]
+	#[precompile::public("batch_some_until_failure(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some_until_failure(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::batch(Mode::BatchSomeUntilFailure, handle, to, value, call_data, gas_limit)
+	}
+
+	#[precompile::public("batchAll(address[],uint256[],bytes[],uint64[])")]
+	#[precompile::public("batch_all(address[],uint256[],bytes[],uint64[])")]
+	fn batch_all(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::batch(Mode::BatchAll, handle, to, value, call_data, gas_limit)
+	}
+
+	/// Call each of `to`. Missing values, call data and gas limits default to zero, empty and
+	/// all the remaining gas; a zero gas limit also forwards all the remaining gas.
+	fn batch(
+		mode: Mode,
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		// Subcalls act on behalf of the caller, which must not be able to lend its identity to
+		// the code of another contract.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		let addresses = Vec::from(to).into_iter().enumerate();
+		let values = Vec::from(value).into_iter().map(Some).chain(repeat(None));
+		let calls_data =
+			Vec::from(call_data).into_iter().map(|data| Some(data.into())).chain(repeat(None));
+		let gas_limits = Vec::from(gas_limit)
+			.into_iter()
+			.map(|limit| if limit == 0 { None } else { Some(limit) })
+			.chain(repeat(None));
+
+		let log_cost = log_subcall_failed(handle.code_address(), 0)
+			.compute_cost()
+			.map_err(|_| revert("failed to compute log cost"))?;
+
+		for ((index, address), (value, (call_data, gas_limit))) in
+			addresses.zip(values.zip(calls_data.zip(gas_limits)))
+		{
+			let address = address.0;
+			let value = value.unwrap_or_default();
+			let call_data: Vec<u8> = call_data.unwrap_or(vec![]);
+
+			// Keep enough gas to log the outcome of the subcall.
+			let forwarded_gas = match (handle.remaining_gas().checked_sub(log_cost), mode) {
+				(Some(remaining), _) => remaining,
+				(None, Mode::BatchAll) =>
+					return Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
+				(None, _) => return Ok(()),
+			};
+
+			// The batch pays for the call itself, and forwards the rest.
+			let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
+			let forwarded_gas = match forwarded_gas.checked_sub(call_cost) {
+				Some(remaining) if gas_limit.map_or(true, |limit| limit <= remaining) =>
+					gas_limit.unwrap_or(remaining),
+				_ => {
+					let log = log_subcall_failed(handle.code_address(), index);
+					handle.record_log_costs(&[&log])?;
+					log.record(handle)?;
+
+					match mode {
+						Mode::BatchAll =>
+							return Err(PrecompileFailure::Error {
+								exit_status: ExitError::OutOfGas,
+							}),
+						Mode::BatchSomeUntilFailure => return Ok(()),
+						Mode::BatchSome => continue,
+					}
+				},
+			};
+
+			handle.record_cost(call_cost)?;
+			let caller = handle.context().caller;
+			let context = Context { caller, address, apparent_value: value };
+			let transfer =
+				(!value.is_zero()).then(|| Transfer { source: caller, target: address, value });
+			let (reason, output) =
+				handle.call(address, transfer, call_data, Some(forwarded_gas), false, &context);
+
+			let log = match reason {
+				ExitReason::Succeed(_) => Some(log_subcall_succeeded(handle.code_address(), index)),
+				ExitReason::Revert(_) | ExitReason::Error(_) =>
+					Some(log_subcall_failed(handle.code_address(), index)),
+				ExitReason::Fatal(_) => None,
+			};
+			if let Some(log) = log {
+				handle.record_log_costs(&[&log])?;
+				log.record(handle)?;
+			}
+
+			match (mode, reason) {
+				(_, ExitReason::Fatal(exit_status)) =>
+					return Err(PrecompileFailure::Fatal { exit_status }),
+				(Mode::BatchAll, ExitReason::Revert(exit_status)) =>
+					return Err(PrecompileFailure::Revert { exit_status, output }),
+				(Mode::BatchAll, ExitReason::Error(exit_status)) =>
+					return Err(PrecompileFailure::Error { exit_status }),
+				(Mode::BatchSomeUntilFailure, ExitReason::Revert(_) | ExitReason::Error(_)) =>
+					return Ok(()),
+				_ => (),
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test, ALICE, BOB, CHARLIE};
+	use fp_evm::{ExitRevert, ExitSucceed, Precompile, PrecompileOutput};
+	use precompile_utils::testing::{MockHandle, Subcall, SubcallOutput};
+	use std::{cell::RefCell, rc::Rc};
+
+	type PCall = BatchPrecompileCall<Test>;
+
+	const BATCH: H160 = H160::repeat_byte(0x01);
+	const SUBCALL_COST: u64 = 1_000;
+	const GAS_LIMIT: u64 = 1_000_000;
+
+	fn subcall_output(reason: ExitReason, output: &[u8]) -> SubcallOutput {
+		SubcallOutput { reason, output: output.to_vec(), cost: SUBCALL_COST, logs: vec![] }
+	}
+
+	/// Runs a batch from `ALICE` against subcalls to `BOB` succeeding and to `CHARLIE`
+	/// reverting, returning its result, its handle and the subcalls it made.
+	fn run(
+		mode: Mode,
+		to: Vec<H160>,
+		value: Vec<U256>,
+		gas_limit: Vec<u64>,
+	) -> (Result<PrecompileOutput, PrecompileFailure>, MockHandle, Vec<Subcall>) {
+		let to = to.into_iter().map(Address).collect::<Vec<_>>().into();
+		let value = value.into();
+		let call_data = vec![BoundedBytes::from(vec![0xde, 0xad])].into();
+		let gas_limit = gas_limit.into();
+		let input = match mode {
+			Mode::BatchSome => PCall::batch_some { to, value, call_data, gas_limit },
+			Mode::BatchSomeUntilFailure =>
+				PCall::batch_some_until_failure { to, value, call_data, gas_limit },
+			Mode::BatchAll => PCall::batch_all { to, value, call_data, gas_limit },
+		};
+
+		let subcalls = Rc::new(RefCell::new(Vec::new()));
+		let context = Context { address: BATCH, caller: ALICE, apparent_value: U256::zero() };
+		let mut handle = MockHandle::new(BATCH, context);
+		handle.gas_limit = GAS_LIMIT;
+		handle.input = input.into();
+		handle.subcall_handle = Some(Box::new({
+			let subcalls = subcalls.clone();
+			move |subcall: Subcall| {
+				let output = if subcall.address == CHARLIE {
+					subcall_output(ExitReason::Revert(ExitRevert::Reverted), b"failed")
+				} else {
+					subcall_output(ExitReason::Succeed(ExitSucceed::Returned), &[])
+				};
+				subcalls.borrow_mut().push(subcall);
+				output
+			}
+		}));
+
+		let result = new_test_ext().execute_with(|| BatchPrecompile::<Test>::execute(&mut handle));
+		let subcalls = subcalls.take();
+		(result, handle, subcalls)
+	}
+
+	fn succeeded(index: usize) -> precompile_utils::testing::PrettyLog {
+		log_subcall_succeeded(BATCH, index).into()
+	}
+
+	fn failed(index: usize) -> precompile_utils::testing::PrettyLog {
+		log_subcall_failed(BATCH, index).into()
+	}
+
+	fn addresses(subcalls: &[Subcall]) -> Vec<H160> {
+		subcalls.iter().map(|subcall| subcall.address).collect()
+	}
+
+	#[test]
+	fn batch_some_skips_failing_subcalls() {
+		let (result, handle, subcalls) =
+			run(Mode::BatchSome, vec![BOB, CHARLIE, BOB], vec![], vec![]);
+		assert!(result.is_ok());
+		assert_eq!(addresses(&subcalls), vec![BOB, CHARLIE, BOB]);
+		assert_eq!(handle.logs, vec![succeeded(0), failed(1), succeeded(2)]);
+	}
+
+	#[test]
+	fn batch_some_until_failure_stops_at_the_failing_subcall() {
+		let (result, handle, subcalls) =
+			run(Mode::BatchSomeUntilFailure, vec![BOB, CHARLIE, BOB], vec![], vec![]);
+		assert!(result.is_ok());
+		assert_eq!(addresses(&subcalls), vec![BOB, CHARLIE]);
+		assert_eq!(handle.logs, vec![succeeded(0), failed(1)]);
+	}
+
+	#[test]
+	fn batch_all_reverts_with_the_failing_subcall() {
+		let (result, _, subcalls) = run(Mode::BatchAll, vec![BOB, CHARLIE, BOB], vec![], vec![]);
+		assert_eq!(
+			result,
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: b"failed".to_vec(),
+			})
+		);
+		assert_eq!(addresses(&subcalls), vec![BOB, CHARLIE]);
+	}
+
+	#[test]
+	fn subcalls_act_as_the_caller() {
+		let value = U256::from(100);
+		let (result, _, subcalls) = run(Mode::BatchAll, vec![BOB], vec![value], vec![]);
+		assert!(result.is_ok());
+		let subcall = &subcalls[0];
+		assert_eq!(subcall.context, Context { address: BOB, caller: ALICE, apparent_value: value });
+		let transfer = subcall.transfer.as_ref().unwrap();
+		assert_eq!((transfer.source, transfer.target, transfer.value), (ALICE, BOB, value));
+		assert_eq!(subcall.input, vec![0xde, 0xad]);
+		assert!(!subcall.is_static);
+	}
+
+	#[test]
+	fn the_batch_pays_for_the_subcalls() {
+		let value = U256::from(100);
+		let call_cost = call_cost(value, <Test as pallet_evm::Config>::config());
+		let log_cost = log_subcall_failed(BATCH, 0).compute_cost().unwrap();
+		let (result, handle, subcalls) =
+			run(Mode::BatchAll, vec![BOB, BOB], vec![value, value], vec![]);
+		assert!(result.is_ok());
+
+		// All the gas is forwarded but the cost of the call and of its log.
+		let first = subcalls[0].target_gas.unwrap();
+		assert_eq!(first, GAS_LIMIT - log_cost - call_cost);
+		// The batch pays for the call as a CALL would, besides the subcall and its log. The mock
+		// handle records the cost of the call once more for running it.
+		let second = subcalls[1].target_gas.unwrap();
+		let first_cost = first - second;
+		assert_eq!(first_cost, 2 * call_cost + SUBCALL_COST + log_cost);
+		assert_eq!(handle.gas_used, 2 * first_cost);
+	}
+
+	#[test]
+	fn gas_limits_bound_the_subcalls() {
+		let (result, _, subcalls) = run(Mode::BatchAll, vec![BOB, BOB], vec![], vec![5_000]);
+		assert!(result.is_ok());
+		assert_eq!(subcalls[0].target_gas, Some(5_000));
+		assert!(subcalls[1].target_gas.unwrap() > 5_000);
+
+		// A gas limit above the remaining gas fails the subcall without running it.
+		let (result, handle, subcalls) =
+			run(Mode::BatchSome, vec![BOB, BOB], vec![], vec![GAS_LIMIT, 0]);
+		assert!(result.is_ok());
+		assert_eq!(addresses(&subcalls), vec![BOB]);
+		assert_eq!(handle.logs, vec![failed(0), succeeded(1)]);
+
+		let (result, _, subcalls) = run(Mode::BatchAll, vec![BOB], vec![], vec![GAS_LIMIT]);
+		assert_eq!(result, Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }));
+		assert!(subcalls.is_empty());
+	}
+
+	#[test]
+	fn rejects_delegate_calls() {
+		let context = Context { address: ALICE, caller: BOB, apparent_value: U256::zero() };
+		let mut handle = MockHandle::new(BATCH, context);
+		handle.input = PCall::batch_all {
+			to: vec![].into(),
+			value: vec![].into(),
+			call_data: vec![].into(),
+			gas_limit: vec![].into(),
+		}
+		.into();
+		let result = new_test_ext().execute_with(|| BatchPrecompile::<Test>::execute(&mut handle));
+		assert_eq!(result, Err(revert("cannot be called with DELEGATECALL or CALLCODE")));
+	}
+}
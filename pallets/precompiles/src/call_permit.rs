@@ -0,0 +1,252 @@
+use fp_evm::{Context, ExitReason, ExitRevert, PrecompileFailure, Transfer};
+use frame_support::{
+	storage::types::{StorageMap, ValueQuery},
+	traits::{ConstU32, Get, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use precompile_utils::{evm::costs::call_cost, prelude::*};
+use sp_core::{hashing::keccak_256, H160, H256, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// The storage prefix of the permit nonces.
+pub struct NoncesPrefix;
+
+impl StorageInstance for NoncesPrefix {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PrecompileCallPermit"
+	}
+}
+
+/// The nonce of the next permit of each account.
+pub type Nonces = StorageMap<NoncesPrefix, Blake2_128Concat, H160, U256, ValueQuery>;
+
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit,uint256 nonce,uint256 deadline)"
+);
+
+const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// The maximum size of the call data of a permit.
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+/// The gas cost of ECRecover, as charged by the precompile at address 1.
+const ECRECOVER_COST: u64 = 3000;
+
+/// The gas cost of hashing `len` bytes with keccak256, as charged by the SHA3 opcode.
+fn keccak_cost(len: usize) -> u64 {
+	30u64.saturating_add(6u64.saturating_mul((len as u64).div_ceil(32)))
+}
+
+/// The gas cost of the domain separator: hashing its name, then its encoding.
+fn domain_separator_cost() -> u64 {
+	keccak_cost(22).saturating_add(keccak_cost(5 * 32))
+}
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+/// Dispatches EVM calls signed off-chain with EIP-712 permits, so that a relayer pays the gas
+/// on behalf of the signer.
+pub struct CallPermitPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> CallPermitPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	// Storage getters
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(Nonces::get(owner.0))
+	}
+
+	#[precompile::public("domainSeparator()")]
+	#[precompile::public("domain_separator()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		handle.record_cost(domain_separator_cost())?;
+		Ok(Self::compute_domain_separator(handle.code_address()).into())
+	}
+
+	// Dispatchable methods
+
+	/// Calls `to` as `from`, with the permit `(v, r, s)` signed by `from`. Returns the output
+	/// of the call, and reverts if it fails.
+	#[precompile::public(
+		"dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn dispatch(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+		data: BoundedBytes<GetCallDataLimit>,
+		gas_limit: u64,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<UnboundedBytes> {
+		let (from, to): (H160, H160) = (from.into(), to.into());
+		let data: Vec<u8> = data.into();
+
+		// The timestamp and the nonce.
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+		let now: u64 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(now / 1000) {
+			return Err(revert("permit expired"));
+		}
+
+		// Hashing the call data, the permit, the domain separator and the digest, then
+		// recovering the signer and hashing its public key.
+		handle.record_cost(
+			keccak_cost(data.len())
+				.saturating_add(keccak_cost(8 * 32))
+				.saturating_add(domain_separator_cost())
+				.saturating_add(keccak_cost(2 + 32 + 32))
+				.saturating_add(ECRECOVER_COST)
+				.saturating_add(keccak_cost(64)),
+		)?;
+
+		let nonce = Nonces::get(from);
+		let permit = generate_permit(
+			Self::compute_domain_separator(handle.code_address()),
+			from,
+			to,
+			value,
+			&data,
+			gas_limit,
+			nonce,
+			deadline,
+		);
+
+		let mut signature = [0u8; 65];
+		signature[0..32].copy_from_slice(&r.0);
+		signature[32..64].copy_from_slice(&s.0);
+		signature[64] = v;
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &permit)
+			.map_err(|_| revert("invalid permit"))?;
+		let signer = H160::from(H256::from(keccak_256(&signer)));
+		if signer.is_zero() || signer != from {
+			return Err(revert("invalid permit"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		Nonces::insert(from, nonce + U256::one());
+
+		// The relayer pays for the call itself, and must forward the whole gas limit.
+		let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
+		let required_gas = gas_limit
+			.checked_add(call_cost)
+			.ok_or_else(|| revert("gas limit overflows"))?;
+		if required_gas > handle.remaining_gas() {
+			return Err(revert("gas limit is too low to dispatch the call"));
+		}
+		handle.record_cost(call_cost)?;
+
+		let context = Context { caller: from, address: to, apparent_value: value };
+		let transfer = (!value.is_zero()).then(|| Transfer { source: from, target: to, value });
+		let (reason, output) = handle.call(to, transfer, data, Some(gas_limit), false, &context);
+
+		match reason {
+			ExitReason::Succeed(_) => Ok(output.into()),
+			ExitReason::Revert(_) =>
+				Err(PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output }),
+			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+		}
+	}
+
+	fn compute_domain_separator(address: H160) -> [u8; 32] {
+		domain_separator(address, <Runtime as pallet_evm::Config>::ChainId::get())
+	}
+}
+
+/// The EIP-712 domain separator of the precompile at `address` on chain `chain_id`.
+fn domain_separator(address: H160, chain_id: u64) -> [u8; 32] {
+	let name = H256::from(keccak_256(b"Call Permit Precompile"));
+	let version = H256::from(keccak256!("1"));
+
+	keccak_256(&solidity::encode_arguments((
+		H256::from(PERMIT_DOMAIN),
+		name,
+		version,
+		U256::from(chain_id),
+		Address(address),
+	)))
+}
+
+/// The EIP-712 digest `from` signs to permit the call.
+#[allow(clippy::too_many_arguments)]
+fn generate_permit(
+	domain_separator: [u8; 32],
+	from: H160,
+	to: H160,
+	value: U256,
+	data: &[u8],
+	gas_limit: u64,
+	nonce: U256,
+	deadline: U256,
+) -> [u8; 32] {
+	let permit = keccak_256(&solidity::encode_arguments((
+		H256::from(PERMIT_TYPEHASH),
+		Address(from),
+		Address(to),
+		value,
+		H256::from(keccak_256(data)),
+		gas_limit,
+		nonce,
+		deadline,
+	)));
+
+	let mut digest = Vec::with_capacity(2 + 32 + 32);
+	digest.extend_from_slice(b"\x19\x01");
+	digest.extend_from_slice(&domain_separator);
+	digest.extend_from_slice(&permit);
+	keccak_256(&digest)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn permit_digest_matches_eip712() {
+		let address = H160::from_low_u64_be(1107);
+		let domain_separator = domain_separator(address, 1);
+		assert_eq!(
+			format!("{:x}", H256::from(domain_separator)),
+			"9a83f0e564a18444bd39c64ac564cb105364a14a0437cf0932477d45085b669e"
+		);
+
+		let digest = generate_permit(
+			domain_separator,
+			H160::repeat_byte(0x11),
+			H160::repeat_byte(0x22),
+			U256::one(),
+			&[0xde, 0xad, 0xbe, 0xef],
+			100_000,
+			U256::zero(),
+			U256::from(1000),
+		);
+		assert_eq!(
+			format!("{:x}", H256::from(digest)),
+			"d629b8a093064690a8b479a7e8aae2571b1574a5850448dc245753676702e718"
+		);
+	}
+
+	#[test]
+	fn keccak_cost_is_charged_per_word() {
+		assert_eq!(keccak_cost(0), 30);
+		assert_eq!(keccak_cost(1), 36);
+		assert_eq!(keccak_cost(32), 36);
+		assert_eq!(keccak_cost(33), 42);
+	}
+}
@@ -0,0 +1,87 @@
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use precompile_utils::prelude::*;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::Dispatchable, PerThing};
+use sp_std::marker::PhantomData;
+
+type BalanceOf<Runtime> = pallet_fee_sharing::BalanceOf<Runtime>;
+
+/// Register contracts for developer gas rebates, and claim the rebates.
+pub struct FeeSharingPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> FeeSharingPrecompile<Runtime>
+where
+	Runtime: pallet_fee_sharing::Config + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_fee_sharing::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: Into<U256>,
+	Runtime::AccountId: Into<H160>,
+{
+	// Storage getters
+
+	/// Returns whether `contract` is registered, and its beneficiary.
+	#[precompile::public("beneficiaryOf(address)")]
+	#[precompile::public("beneficiary_of(address)")]
+	#[precompile::view]
+	fn beneficiary_of(
+		handle: &mut impl PrecompileHandle,
+		contract: Address,
+	) -> EvmResult<(bool, Address)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let beneficiary = pallet_fee_sharing::Pallet::<Runtime>::beneficiary_of(contract.0);
+		Ok((beneficiary.is_some(), Address(beneficiary.map(Into::into).unwrap_or_default())))
+	}
+
+	#[precompile::public("accrued(address)")]
+	#[precompile::view]
+	fn accrued(handle: &mut impl PrecompileHandle, beneficiary: Address) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let beneficiary = Runtime::AddressMapping::into_account_id(beneficiary.0);
+		Ok(pallet_fee_sharing::Pallet::<Runtime>::accrued(&beneficiary).into())
+	}
+
+	/// Returns the percentage of the fees paid to call a registered contract accrued to its
+	/// beneficiary.
+	#[precompile::public("developerShare()")]
+	#[precompile::public("developer_share()")]
+	#[precompile::view]
+	fn developer_share(handle: &mut impl PrecompileHandle) -> EvmResult<u8> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(pallet_fee_sharing::Pallet::<Runtime>::developer_share().deconstruct())
+	}
+
+	// Dispatchable methods
+
+	#[precompile::public("register(address,address)")]
+	fn register(
+		handle: &mut impl PrecompileHandle,
+		contract: Address,
+		beneficiary: Address,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let beneficiary = Runtime::AddressMapping::into_account_id(beneficiary.0);
+		let call =
+			pallet_fee_sharing::Call::<Runtime>::register { contract: contract.0, beneficiary };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("unregister(address)")]
+	fn unregister(handle: &mut impl PrecompileHandle, contract: Address) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_fee_sharing::Call::<Runtime>::unregister { contract: contract.0 };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("claim()")]
+	fn claim(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_fee_sharing::Call::<Runtime>::claim {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,138 @@
+use codec::DecodeLimit;
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use pallet_governance::{Conviction, ReferendumInfo, Vote};
+use precompile_utils::prelude::*;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::Dispatchable, SaturatedConversion};
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
+
+type BalanceOf<Runtime> = pallet_governance::BalanceOf<Runtime>;
+
+type ProposalCallOf<Runtime> = <Runtime as pallet_governance::Config>::RuntimeCall;
+
+/// The maximum nesting of the calls decoded from EVM input.
+const MAX_CALL_DEPTH: u32 = 32;
+
+/// Submit proposals to referenda and vote in them with veTAKER.
+///
+/// Proposals are SCALE encoded runtime calls. Referendum states are 0 for unknown, 1 for ongoing,
+/// 2 for approved and enacted, 3 for rejected or cancelled and 4 for approved and awaiting
+/// enactment. Convictions go from 0, a tenth of the balance not locked after the referendum, to 6.
+pub struct GovernancePrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> GovernancePrecompile<Runtime>
+where
+	Runtime: pallet_governance::Config + pallet_evm::Config + frame_system::Config,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_governance::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	Runtime::AccountId: Into<H160>,
+{
+	// Storage getters
+
+	/// Returns the number of referenda submitted so far.
+	#[precompile::public("referendumCount()")]
+	#[precompile::public("referendum_count()")]
+	#[precompile::view]
+	fn referendum_count(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(pallet_governance::Pallet::<Runtime>::referendum_count())
+	}
+
+	/// Returns the state of a referendum, the block it ends or ended at, or is enacted at if it
+	/// awaits enactment, and the ayes, nays and turnout of an ongoing one.
+	#[precompile::public("referendumInfo(uint32)")]
+	#[precompile::public("referendum_info(uint32)")]
+	#[precompile::view]
+	fn referendum_info(
+		handle: &mut impl PrecompileHandle,
+		index: u32,
+	) -> EvmResult<(u8, u32, U256, U256, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(match pallet_governance::Pallet::<Runtime>::referendum_info(index) {
+			None => (0, 0, U256::zero(), U256::zero(), U256::zero()),
+			Some(ReferendumInfo::Ongoing(status)) => (
+				1,
+				status.end.saturated_into(),
+				status.tally.ayes.into(),
+				status.tally.nays.into(),
+				status.tally.turnout.into(),
+			),
+			Some(ReferendumInfo::Approved { enactment, .. }) =>
+				(4, enactment.saturated_into(), U256::zero(), U256::zero(), U256::zero()),
+			Some(ReferendumInfo::Finished { approved, end }) => {
+				let state = if approved { 2 } else { 3 };
+				(state, end.saturated_into(), U256::zero(), U256::zero(), U256::zero())
+			},
+		})
+	}
+
+	/// Returns whether `voter` voted in a referendum and has not unlocked it yet, and its vote.
+	#[precompile::public("voteOf(uint32,address)")]
+	#[precompile::public("vote_of(uint32,address)")]
+	#[precompile::view]
+	fn vote_of(
+		handle: &mut impl PrecompileHandle,
+		index: u32,
+		voter: Address,
+	) -> EvmResult<(bool, bool, U256, u8)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let voter = Runtime::AddressMapping::into_account_id(voter.0);
+		Ok(match pallet_governance::Pallet::<Runtime>::vote_of(index, &voter) {
+			Some(vote) => (true, vote.aye, vote.balance.into(), vote.conviction as u8),
+			None => (false, false, U256::zero(), 0),
+		})
+	}
+
+	// Dispatchable methods
+
+	#[precompile::public("submit(bytes)")]
+	fn submit(handle: &mut impl PrecompileHandle, proposal: UnboundedBytes) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let proposal = Self::decode_call(proposal).in_field("proposal")?;
+		let call = pallet_governance::Call::<Runtime>::submit { proposal };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("vote(uint32,bool,uint256,uint8)")]
+	fn vote(
+		handle: &mut impl PrecompileHandle,
+		index: u32,
+		aye: bool,
+		balance: U256,
+		conviction: u8,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let balance: BalanceOf<Runtime> = balance
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("balance"))?;
+		let conviction = Conviction::try_from(conviction)
+			.map_err(|_| RevertReason::custom("unknown conviction").in_field("conviction"))?;
+		let vote = Vote { aye, balance, conviction };
+		let call = pallet_governance::Call::<Runtime>::vote { index, vote };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("unlock(address)")]
+	fn unlock(handle: &mut impl PrecompileHandle, target: Address) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let target = Runtime::AddressMapping::into_account_id(target.0);
+		let call = pallet_governance::Call::<Runtime>::unlock { target };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	fn decode_call(call: UnboundedBytes) -> MayRevert<Box<ProposalCallOf<Runtime>>> {
+		let call: Vec<u8> = call.into();
+		ProposalCallOf::<Runtime>::decode_all_with_depth_limit(MAX_CALL_DEPTH, &mut &*call)
+			.map(Box::new)
+			.map_err(|_| RevertReason::custom("failed to decode call").into())
+	}
+}
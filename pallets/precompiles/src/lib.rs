@@ -2,5 +2,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod asset_currency;
+pub mod batch;
+pub mod call_permit;
+pub mod fee_sharing;
+pub mod governance;
+#[cfg(test)]
+mod mock;
+pub mod multisig;
 pub mod native_currency;
+pub mod provider;
 pub mod staking;
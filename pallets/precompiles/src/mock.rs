@@ -0,0 +1,96 @@
+//! A runtime to test the precompiles against.
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32},
+	weights::Weight,
+};
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, IdentityAddressMapping};
+use sp_core::{H160, U256};
+use sp_runtime::{traits::IdentityLookup, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
+		EVM: pallet_evm,
+		Multisig: pallet_multisig,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountId = H160;
+	type Lookup = IdentityLookup<H160>;
+	type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+impl pallet_timestamp::Config for Test {}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = u128;
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+}
+
+impl pallet_evm::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockGasLimit = BlockGasLimit;
+	type ChainId = frame_support::traits::ConstU64<42>;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type CallOrigin = EnsureAddressRoot<H160>;
+	type WithdrawOrigin = EnsureAddressNever<H160>;
+	type AddressMapping = IdentityAddressMapping;
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type OnChargeTransaction = ();
+	type FindAuthor = ();
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type OnCreate = ();
+	type CreateOriginFilter = ();
+	type CreateInnerOriginFilter = ();
+	type GasLimitPovSizeRatio = frame_support::traits::ConstU64<4>;
+	type Timestamp = Timestamp;
+	type SuicideQuickClearLimit = ConstU32<0>;
+	type WeightInfo = ();
+}
+
+impl pallet_multisig::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type DepositBase = ConstU128<100>;
+	type DepositFactor = ConstU128<10>;
+	type MaxSignatories = ConstU32<3>;
+	type WeightInfo = ();
+}
+
+pub const ALICE: H160 = H160::repeat_byte(0xaa);
+pub const BOB: H160 = H160::repeat_byte(0xbb);
+pub const CHARLIE: H160 = H160::repeat_byte(0xcc);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
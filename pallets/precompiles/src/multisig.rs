@@ -0,0 +1,279 @@
+use codec::DecodeLimit;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::{Currency, Get},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_evm::AddressMapping;
+use pallet_multisig::Timepoint;
+use precompile_utils::prelude::*;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Dispatchable, SaturatedConversion};
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
+
+type BalanceOf<Runtime> = <<Runtime as pallet_multisig::Config>::Currency as Currency<
+	<Runtime as frame_system::Config>::AccountId,
+>>::Balance;
+
+type MultisigCallOf<Runtime> = <Runtime as pallet_multisig::Config>::RuntimeCall;
+
+/// The maximum nesting of the calls decoded from EVM input.
+const MAX_CALL_DEPTH: u32 = 32;
+
+/// The gas charged per signatory for mapping it to an account, sorting and hashing it.
+pub const SIGNATORY_GAS: u64 = 200;
+/// The gas charged for hashing the signatories into the multisig account.
+pub const HASH_GAS: u64 = 60;
+/// The gas charged for computing a deposit from the configured constants.
+pub const DEPOSIT_GAS: u64 = 40;
+
+/// At most `MaxSignatories` signatories are accepted, as `pallet_multisig` does.
+pub struct GetMaxSignatories<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime: pallet_multisig::Config> Get<u32> for GetMaxSignatories<Runtime> {
+	fn get() -> u32 {
+		<Runtime as pallet_multisig::Config>::MaxSignatories::get()
+	}
+}
+
+type Signatories<Runtime> = BoundedVec<Address, GetMaxSignatories<Runtime>>;
+
+/// Operate the multisig accounts of `pallet_multisig` from EVM accounts.
+///
+/// Calls are SCALE encoded runtime calls. Timepoints are given as `(height, index)`, with a
+/// zero `height` for none.
+pub struct MultisigPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> MultisigPrecompile<Runtime>
+where
+	Runtime: pallet_multisig::Config + pallet_evm::Config + frame_system::Config,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_multisig::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: Into<U256>,
+	Runtime::AccountId: Into<H160>,
+{
+	// Storage getters
+
+	/// Returns the multisig account of `signatories` and `threshold`.
+	#[precompile::public("multisigAccount(address[],uint16)")]
+	#[precompile::public("multisig_account(address[],uint16)")]
+	#[precompile::view]
+	fn multisig_account(
+		handle: &mut impl PrecompileHandle,
+		signatories: Signatories<Runtime>,
+		threshold: u16,
+	) -> EvmResult<Address> {
+		let signatories = Self::signatories(handle, signatories)?;
+		handle.record_cost(HASH_GAS)?;
+		let multisig =
+			pallet_multisig::Pallet::<Runtime>::multi_account_id(&signatories, threshold);
+		Ok(Address(multisig.into()))
+	}
+
+	/// Returns the pending operation of `multisig` on the call of `call_hash`: whether it
+	/// exists, its timepoint, the deposit held, the depositor and the approvals so far.
+	#[precompile::public("pending(address,bytes32)")]
+	#[precompile::view]
+	fn pending(
+		handle: &mut impl PrecompileHandle,
+		multisig: Address,
+		call_hash: H256,
+	) -> EvmResult<(bool, u32, u32, U256, Address, Vec<Address>)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let multisig = Runtime::AddressMapping::into_account_id(multisig.0);
+		let Some(pending) = pallet_multisig::Multisigs::<Runtime>::get(&multisig, call_hash.0)
+		else {
+			return Ok((false, 0, 0, U256::zero(), Address(H160::zero()), Vec::new()));
+		};
+		Ok((
+			true,
+			pending.when.height.saturated_into(),
+			pending.when.index,
+			pending.deposit.into(),
+			Address(pending.depositor.into()),
+			pending.approvals.into_iter().map(|who| Address(who.into())).collect(),
+		))
+	}
+
+	/// Returns the deposit held from the first approver of an operation with `threshold`.
+	#[precompile::public("deposit(uint16)")]
+	#[precompile::view]
+	fn deposit(handle: &mut impl PrecompileHandle, threshold: u16) -> EvmResult<U256> {
+		handle.record_cost(DEPOSIT_GAS)?;
+		let deposit = <Runtime as pallet_multisig::Config>::DepositBase::get() +
+			<Runtime as pallet_multisig::Config>::DepositFactor::get() *
+				BalanceOf::<Runtime>::from(threshold);
+		Ok(deposit.into())
+	}
+
+	// Dispatchable methods
+
+	#[precompile::public("asMultiThreshold1(address[],bytes)")]
+	#[precompile::public("as_multi_threshold_1(address[],bytes)")]
+	fn as_multi_threshold_1(
+		handle: &mut impl PrecompileHandle,
+		other_signatories: Signatories<Runtime>,
+		call: UnboundedBytes,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_multisig::Call::<Runtime>::as_multi_threshold_1 {
+			other_signatories: Self::signatories(handle, other_signatories)?,
+			call: Self::decode_call(call).in_field("call")?,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	/// Approves the call and, once `threshold` is reached, dispatches it from the multisig
+	/// account. The call weight is the maximum weight it may take.
+	#[precompile::public("asMulti(uint16,address[],uint32,uint32,bytes)")]
+	#[precompile::public("as_multi(uint16,address[],uint32,uint32,bytes)")]
+	fn as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: Signatories<Runtime>,
+		height: u32,
+		index: u32,
+		call: UnboundedBytes,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = Self::decode_call(call).in_field("call")?;
+		let max_weight = call.get_dispatch_info().weight;
+		let call = pallet_multisig::Call::<Runtime>::as_multi {
+			threshold,
+			other_signatories: Self::signatories(handle, other_signatories)?,
+			maybe_timepoint: Self::timepoint(height, index),
+			call,
+			max_weight,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("approveAsMulti(uint16,address[],uint32,uint32,bytes32)")]
+	#[precompile::public("approve_as_multi(uint16,address[],uint32,uint32,bytes32)")]
+	fn approve_as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: Signatories<Runtime>,
+		height: u32,
+		index: u32,
+		call_hash: H256,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_multisig::Call::<Runtime>::approve_as_multi {
+			threshold,
+			other_signatories: Self::signatories(handle, other_signatories)?,
+			maybe_timepoint: Self::timepoint(height, index),
+			call_hash: call_hash.0,
+			max_weight: Weight::zero(),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("cancelAsMulti(uint16,address[],uint32,uint32,bytes32)")]
+	#[precompile::public("cancel_as_multi(uint16,address[],uint32,uint32,bytes32)")]
+	fn cancel_as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: Signatories<Runtime>,
+		height: u32,
+		index: u32,
+		call_hash: H256,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let timepoint = Self::timepoint(height, index)
+			.ok_or_else(|| RevertReason::custom("timepoint is required").in_field("height"))?;
+		let call = pallet_multisig::Call::<Runtime>::cancel_as_multi {
+			threshold,
+			other_signatories: Self::signatories(handle, other_signatories)?,
+			timepoint,
+			call_hash: call_hash.0,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	/// The account ids of `signatories`, sorted as `pallet_multisig` requires.
+	fn signatories(
+		handle: &mut impl PrecompileHandle,
+		signatories: Signatories<Runtime>,
+	) -> EvmResult<Vec<Runtime::AccountId>> {
+		let signatories: Vec<Address> = signatories.into();
+		handle.record_cost(SIGNATORY_GAS.saturating_mul(signatories.len() as u64))?;
+		let mut signatories: Vec<_> = signatories
+			.into_iter()
+			.map(|who| Runtime::AddressMapping::into_account_id(who.0))
+			.collect();
+		signatories.sort();
+		Ok(signatories)
+	}
+
+	fn timepoint(height: u32, index: u32) -> Option<Timepoint<BlockNumberFor<Runtime>>> {
+		(height != 0).then(|| Timepoint { height: height.into(), index })
+	}
+
+	fn decode_call(call: UnboundedBytes) -> MayRevert<Box<MultisigCallOf<Runtime>>> {
+		let call: Vec<u8> = call.into();
+		MultisigCallOf::<Runtime>::decode_all_with_depth_limit(MAX_CALL_DEPTH, &mut &*call)
+			.map(Box::new)
+			.map_err(|_| RevertReason::custom("failed to decode call").into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test, ALICE, BOB, CHARLIE};
+	use fp_evm::{Context, Precompile, PrecompileFailure, PrecompileOutput};
+	use precompile_utils::testing::MockHandle;
+
+	type PCall = MultisigPrecompileCall<Test>;
+
+	const MULTISIG: H160 = H160::repeat_byte(0x02);
+
+	fn run(input: PCall) -> (Result<PrecompileOutput, PrecompileFailure>, MockHandle) {
+		let context = Context { address: MULTISIG, caller: ALICE, apparent_value: U256::zero() };
+		let mut handle = MockHandle::new(MULTISIG, context);
+		handle.gas_limit = 1_000_000;
+		handle.input = input.into();
+		let result =
+			new_test_ext().execute_with(|| MultisigPrecompile::<Test>::execute(&mut handle));
+		(result, handle)
+	}
+
+	#[test]
+	fn multisig_account_is_charged_per_signatory() {
+		let signatories = vec![Address(BOB), Address(ALICE)];
+		let (result, handle) =
+			run(PCall::multisig_account { signatories: signatories.into(), threshold: 2 });
+
+		let account = pallet_multisig::Pallet::<Test>::multi_account_id(&[ALICE, BOB], 2);
+		assert_eq!(result.unwrap().output, solidity::encode_return_value(Address(account)));
+		assert_eq!(handle.gas_used, 2 * SIGNATORY_GAS + HASH_GAS);
+	}
+
+	#[test]
+	fn signatories_are_bounded() {
+		let signatories = vec![Address(ALICE), Address(BOB), Address(CHARLIE), Address(MULTISIG)];
+		let (result, handle) =
+			run(PCall::multisig_account { signatories: signatories.into(), threshold: 2 });
+
+		assert!(result.is_err());
+		assert_eq!(handle.gas_used, 0);
+	}
+
+	#[test]
+	fn deposit_is_charged() {
+		let (result, handle) = run(PCall::deposit { threshold: 3 });
+
+		assert_eq!(result.unwrap().output, solidity::encode_return_value(U256::from(130)));
+		assert_eq!(handle.gas_used, DEPOSIT_GAS);
+	}
+}
@@ -0,0 +1,139 @@
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use pallet_provider::types::ProviderId;
+use precompile_utils::prelude::*;
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::Dispatchable;
+use sp_std::marker::PhantomData;
+
+type BalanceOf<Runtime> = pallet_provider::BalanceOf<Runtime>;
+
+pub struct ProviderPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ProviderPrecompile<Runtime>
+where
+	Runtime: pallet_provider::Config + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_provider::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	Runtime::AccountId: Into<H160>,
+{
+	// Storage getters
+
+	#[precompile::public("providerOf(address)")]
+	#[precompile::public("provider_of(address)")]
+	#[precompile::view]
+	fn provider_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<(bool, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner = Runtime::AddressMapping::into_account_id(owner.0);
+		let pid = pallet_provider::Pallet::<Runtime>::provider_of(&owner);
+		Ok((pid.is_some(), pid.unwrap_or_default().into()))
+	}
+
+	/// Returns the owner, cap pledge, total pledge, number of devices, total punishment, total
+	/// rewards and unpaid rewards of a provider.
+	#[precompile::public("provider(uint256)")]
+	#[precompile::view]
+	fn provider(
+		handle: &mut impl PrecompileHandle,
+		pid: U256,
+	) -> EvmResult<(Address, U256, U256, U256, U256, U256, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let pid = Self::u256_to_pid(pid).in_field("pid")?;
+		let provider = pallet_provider::Pallet::<Runtime>::providers(pid)
+			.ok_or_else(|| revert("provider not found"))?;
+		Ok((
+			Address(provider.owner.into()),
+			provider.cap_pledge.into(),
+			provider.total_pledge.into(),
+			provider.devices_num.into(),
+			provider.total_punishment.into(),
+			provider.total_rewards.into(),
+			provider.unpaid_rewards.into(),
+		))
+	}
+
+	#[precompile::public("hasDevice(uint256,bytes32)")]
+	#[precompile::public("has_device(uint256,bytes32)")]
+	#[precompile::view]
+	fn has_device(handle: &mut impl PrecompileHandle, pid: U256, device: H256) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let pid = Self::u256_to_pid(pid).in_field("pid")?;
+		Ok(pallet_provider::Pallet::<Runtime>::devices(pid, device.0).is_some())
+	}
+
+	// Dispatchable methods
+
+	#[precompile::public("register()")]
+	fn register(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_provider::Call::<Runtime>::register {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("deregister()")]
+	fn deregister(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_provider::Call::<Runtime>::deregister {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("pledge(uint256)")]
+	fn pledge(handle: &mut impl PrecompileHandle, value: U256) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let amount = Self::u256_to_amount(value).in_field("value")?;
+		let call = pallet_provider::Call::<Runtime>::pledge { amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("unpledge(uint256)")]
+	fn unpledge(handle: &mut impl PrecompileHandle, value: U256) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let amount = Self::u256_to_amount(value).in_field("value")?;
+		let call = pallet_provider::Call::<Runtime>::unpledge { amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("addDevice(bytes32)")]
+	#[precompile::public("add_device(bytes32)")]
+	fn add_device(handle: &mut impl PrecompileHandle, device: H256) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_provider::Call::<Runtime>::add_device { device: device.0 };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("removeDevice(bytes32)")]
+	#[precompile::public("remove_device(bytes32)")]
+	fn remove_device(handle: &mut impl PrecompileHandle, device: H256) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_provider::Call::<Runtime>::remove_device { device: device.0 };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("claimRewards()")]
+	#[precompile::public("claim_rewards()")]
+	fn claim_rewards(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_provider::Call::<Runtime>::claim_rewards {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime>> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").into())
+	}
+
+	fn u256_to_pid(value: U256) -> MayRevert<ProviderId> {
+		value.try_into().map_err(|_| RevertReason::value_is_too_large("provider id").into())
+	}
+}
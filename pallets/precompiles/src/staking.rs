@@ -1,10 +1,11 @@
-use fp_evm::ExitError;
+use fp_evm::{ExitError, Log};
 use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
 use pallet_evm::AddressMapping;
-use pallet_staking::RewardDestination;
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_staking::{DelegatedOperation, DelegatedOperations, RewardDestination};
 use precompile_utils::prelude::*;
 use sp_core::{Decode, H160, U256};
-use sp_runtime::traits::Dispatchable;
+use sp_runtime::traits::{Dispatchable, Saturating};
 use sp_runtime::traits::StaticLookup;
 use sp_runtime::{PerThing, Perbill, SaturatedConversion};
 use sp_std::marker::PhantomData;
@@ -12,6 +13,40 @@ use sp_std::{vec, vec::Vec};
 
 type BalanceOf<Runtime> = <Runtime as pallet_staking::Config>::CurrencyBalance;
 
+pub const LOG_BONDED: [u8; 32] = keccak256!("Bonded(address,uint256)");
+pub const LOG_UNBONDED: [u8; 32] = keccak256!("Unbonded(address,uint256)");
+pub const LOG_NOMINATED: [u8; 32] = keccak256!("Nominated(address,address[])");
+pub const LOG_REWARD_PAID: [u8; 32] = keccak256!("RewardPaid(address,uint256)");
+pub const LOG_CHILLED: [u8; 32] = keccak256!("Chilled(address)");
+pub const LOG_PAYEE_SET: [u8; 32] = keccak256!("PayeeSet(address,uint8)");
+
+/// The code of `RewardDestination::AutoCompound`, taken by `setPayee` and returned by `payee`.
+pub const PAYEE_AUTO_COMPOUND: u8 = 4;
+
+fn log_bonded(address: impl Into<H160>, staker: H160, amount: U256) -> Log {
+	log2(address, LOG_BONDED, staker, solidity::encode_event_data(amount))
+}
+
+fn log_unbonded(address: impl Into<H160>, staker: H160, amount: U256) -> Log {
+	log2(address, LOG_UNBONDED, staker, solidity::encode_event_data(amount))
+}
+
+fn log_nominated(address: impl Into<H160>, nominator: H160, validators: Vec<Address>) -> Log {
+	log2(address, LOG_NOMINATED, nominator, solidity::encode_event_data(validators))
+}
+
+fn log_reward_paid(address: impl Into<H160>, validator: H160, era: u32) -> Log {
+	log2(address, LOG_REWARD_PAID, validator, solidity::encode_event_data(U256::from(era)))
+}
+
+fn log_chilled(address: impl Into<H160>, staker: H160) -> Log {
+	log2(address, LOG_CHILLED, staker, Vec::new())
+}
+
+fn log_payee_set(address: impl Into<H160>, staker: H160, payee: u8) -> Log {
+	log2(address, LOG_PAYEE_SET, staker, solidity::encode_event_data(payee))
+}
+
 pub struct StakingPrecompile<Runtime>(PhantomData<Runtime>);
 
 #[precompile_utils::precompile]
@@ -88,6 +123,7 @@ where
 			RewardDestination::Stash => H160::from_low_u64_be(2),
 			RewardDestination::Controller => H160::from_low_u64_be(3),
 			RewardDestination::Account(acc) => acc.into(),
+			RewardDestination::AutoCompound => H160::from_low_u64_be(PAYEE_AUTO_COMPOUND.into()),
 			RewardDestination::None => H160::from_low_u64_be(0),
 		};
 		Ok(Address(res))
@@ -275,6 +311,25 @@ where
 		Ok(reward.into())
 	}
 
+	#[precompile::public("delegation(address)")]
+	#[precompile::view]
+	fn delegation(
+		handle: &mut impl PrecompileHandle,
+		delegate: Address,
+	) -> EvmResult<(Address, Vec<u8>, U256)> {
+		let account_id = Runtime::AddressMapping::into_account_id(delegate.0);
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let res = match pallet_staking::Pallet::<Runtime>::delegations(&account_id) {
+			Some(delegation) => (
+				Address(delegation.stash.into()),
+				delegation.operations.into_iter().map(Self::operation_to_u8).collect(),
+				delegation.expiry.map(|e| e.saturated_into::<u128>()).unwrap_or_default().into(),
+			),
+			None => (Address(H160::zero()), Vec::new(), U256::zero()),
+		};
+		Ok(res)
+	}
+
 	#[precompile::public("erasTotalStake(uint256)")]
 	#[precompile::public("eras_total_stake(uint256)")]
 	#[precompile::view]
@@ -298,11 +353,8 @@ where
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let value: BalanceOf<Runtime> = Self::u256_to_amount(bond_value).in_field("bond_value")?;
 
-		let payee = match payee {
-			0 => RewardDestination::Staked,
-			1 => RewardDestination::Stash,
-			_ => RewardDestination::default(),
-		};
+		let code = payee;
+		let payee = Self::reward_destination(payee).in_field("payment_destination")?;
 		let controller: pallet_staking::AccountIdLookupOf<Runtime> = origin.clone().into();
 		let bond_call = pallet_staking::Call::<Runtime>::bond { controller, value, payee }.into();
 		let targets = accounts
@@ -312,7 +364,18 @@ where
 		let nominate_call = pallet_staking::Call::<Runtime>::nominate { targets }.into();
 		let calls = vec![bond_call, nominate_call];
 		let batch_call = pallet_utility::Call::<Runtime>::batch_all { calls };
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), batch_call)?;
+		let (total, _) = Self::bond_of(handle, &origin)?;
+		let nominated = log_nominated(handle.context().address, handle.context().caller, accounts);
+		let payee_set = log_payee_set(handle.context().address, handle.context().caller, code);
+		handle.record_log_costs(&[&nominated, &payee_set])?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), batch_call)?;
+
+		let (bonded, _) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_bonded(address, staker, bonded.saturating_sub(total).into()).record(handle)?;
+		payee_set.record(handle)?;
+		nominated.record(handle)?;
 		Ok(())
 	}
 
@@ -329,11 +392,8 @@ where
 	) -> EvmResult {
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let value: BalanceOf<Runtime> = Self::u256_to_amount(bond_value).in_field("bond_value")?;
-		let payee = match payee {
-			0 => RewardDestination::Staked,
-			1 => RewardDestination::Stash,
-			_ => RewardDestination::default(),
-		};
+		let code = payee;
+		let payee = Self::reward_destination(payee).in_field("payment_destination")?;
 		let commission = sp_runtime::Perbill::from_percent(commission);
 		let keys = Runtime::Keys::decode(&mut raw_keys.as_bytes())
 			.map_err(|_| ExitError::Other("Incorrect session keys".into()))?;
@@ -348,7 +408,16 @@ where
 		let calls = vec![bond_call, set_session_key_call, validate_call];
 		let batch_call = pallet_utility::Call::<Runtime>::batch_all { calls };
 
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), batch_call)?;
+		let (total, _) = Self::bond_of(handle, &origin)?;
+		let payee_set = log_payee_set(handle.context().address, handle.context().caller, code);
+		handle.record_log_costs(&[&payee_set])?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), batch_call)?;
+
+		let (bonded, _) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_bonded(address, staker, bonded.saturating_sub(total).into()).record(handle)?;
+		payee_set.record(handle)?;
 		Ok(())
 	}
 
@@ -356,7 +425,11 @@ where
 	fn chill(handle: &mut impl PrecompileHandle) -> EvmResult {
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let call = pallet_staking::Call::<Runtime>::chill {};
+		let chilled = log_chilled(handle.context().address, handle.context().caller);
+		handle.record_log_costs(&[&chilled])?;
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		chilled.record(handle)?;
 		Ok(())
 	}
 
@@ -366,7 +439,13 @@ where
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let max_additional: BalanceOf<Runtime> = Self::u256_to_amount(value).in_field("value")?;
 		let call = pallet_staking::Call::<Runtime>::bond_extra { max_additional };
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		let (total, _) = Self::bond_of(handle, &origin)?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), call)?;
+
+		let (bonded, _) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_bonded(address, staker, bonded.saturating_sub(total).into()).record(handle)?;
 		Ok(())
 	}
 
@@ -375,7 +454,13 @@ where
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let value: BalanceOf<Runtime> = Self::u256_to_amount(bond_value).in_field("bond_value")?;
 		let call = pallet_staking::Call::<Runtime>::unbond { value };
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		let (_, active) = Self::bond_of(handle, &origin)?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), call)?;
+
+		let (_, bonded) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_unbonded(address, staker, active.saturating_sub(bonded).into()).record(handle)?;
 		Ok(())
 	}
 
@@ -389,15 +474,22 @@ where
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let validator_stash = Runtime::AddressMapping::into_account_id(who.0);
 		let mut calls = Vec::new();
+		let mut logs = Vec::new();
 		for era in era_indexs {
 			let call = pallet_staking::Call::<Runtime>::payout_stakers {
 				validator_stash: validator_stash.clone(),
 				era,
 			};
 			calls.push(call.into());
+			logs.push(log_reward_paid(handle.context().address, who.0, era));
 		}
 		let call = pallet_utility::Call::<Runtime>::batch_all { calls };
+		handle.record_log_costs(&logs.iter().collect::<Vec<_>>())?;
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		for log in logs {
+			log.record(handle)?;
+		}
 		Ok(())
 	}
 
@@ -405,13 +497,42 @@ where
 	#[precompile::public("set_payee(uint256)")]
 	fn set_payee(handle: &mut impl PrecompileHandle, payee: u8) -> EvmResult {
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-		let payee = match payee {
-			0 => RewardDestination::Staked,
-			1 => RewardDestination::Stash,
-			_ => RewardDestination::default(),
-		};
+		let payee_set = log_payee_set(handle.context().address, handle.context().caller, payee);
+		let payee = Self::reward_destination(payee).in_field("payment_destination")?;
+		handle.record_log_costs(&[&payee_set])?;
 		let call = pallet_staking::Call::<Runtime>::set_payee { payee };
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		payee_set.record(handle)?;
+		Ok(())
+	}
+
+	#[precompile::public("setPayee(uint256,uint256,uint256)")]
+	#[precompile::public("set_payee(uint256,uint256,uint256)")]
+	fn set_payee_with_policy(
+		handle: &mut impl PrecompileHandle,
+		payee: u8,
+		threshold: U256,
+		stake: u32,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		if payee != PAYEE_AUTO_COMPOUND {
+			return Err(revert("only auto compounding takes a policy"));
+		}
+		if stake > 100 {
+			return Err(RevertReason::custom("stake share is a percentage")
+				.in_field("stake")
+				.into());
+		}
+		let threshold: BalanceOf<Runtime> =
+			Self::u256_to_amount(threshold).in_field("threshold")?;
+		let stake = Perbill::from_percent(stake);
+		let call = pallet_staking::Call::<Runtime>::set_auto_compound { threshold, stake };
+		let payee_set = log_payee_set(handle.context().address, handle.context().caller, payee);
+		handle.record_log_costs(&[&payee_set])?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		payee_set.record(handle)?;
 		Ok(())
 	}
 
@@ -435,7 +556,11 @@ where
 			.map(|validator| Runtime::AddressMapping::into_account_id(validator.0).into())
 			.collect();
 		let call = pallet_staking::Call::<Runtime>::nominate { targets };
+		let nominated = log_nominated(handle.context().address, handle.context().caller, accounts);
+		handle.record_log_costs(&[&nominated])?;
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+
+		nominated.record(handle)?;
 		Ok(())
 	}
 
@@ -460,7 +585,9 @@ where
 		raw_keys: UnboundedBytes,
 		proof: UnboundedBytes,
 	) -> EvmResult {
-		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let origin =
+			Self::operating_account(handle, caller, &[DelegatedOperation::SessionKeys])?;
 		let keys = Runtime::Keys::decode(&mut raw_keys.as_bytes())
 			.map_err(|_| ExitError::Other("Incorrect session keys".into()))?;
 		let call =
@@ -478,7 +605,12 @@ where
 		raw_keys: UnboundedBytes,
 		proof: UnboundedBytes,
 	) -> EvmResult {
-		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let origin = Self::operating_account(
+			handle,
+			caller,
+			&[DelegatedOperation::Validate, DelegatedOperation::SessionKeys],
+		)?;
 		let commission = sp_runtime::Perbill::from_percent(commission);
 		let prefs = pallet_staking::ValidatorPrefs { commission, blocked: !can_nominated };
 		let validate_call = pallet_staking::Call::<Runtime>::validate { prefs }.into();
@@ -503,7 +635,16 @@ where
 		let unbond_call = pallet_staking::Call::<Runtime>::unbond { value }.into();
 		let calls = vec![chill_call, unbond_call];
 		let call = pallet_utility::Call::<Runtime>::batch_all { calls };
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		let (_, active) = Self::bond_of(handle, &origin)?;
+		let chilled = log_chilled(handle.context().address, handle.context().caller);
+		handle.record_log_costs(&[&chilled])?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), call)?;
+
+		let (_, bonded) = Self::bond_of(handle, &origin)?;
+		chilled.record(handle)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_unbonded(address, staker, active.saturating_sub(bonded).into()).record(handle)?;
 		Ok(())
 	}
 
@@ -524,7 +665,16 @@ where
 		let nominate_call = pallet_staking::Call::<Runtime>::nominate { targets }.into();
 		let calls = vec![bond_extra_call, nominate_call];
 		let call = pallet_utility::Call::<Runtime>::batch_all { calls };
-		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		let (total, _) = Self::bond_of(handle, &origin)?;
+		let nominated = log_nominated(handle.context().address, handle.context().caller, accounts);
+		handle.record_log_costs(&[&nominated])?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), call)?;
+
+		let (bonded, _) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_bonded(address, staker, bonded.saturating_sub(total).into()).record(handle)?;
+		nominated.record(handle)?;
 		Ok(())
 	}
 
@@ -552,10 +702,122 @@ where
 		let calls = vec![bond_extra_call, set_session_key_call, validate_call];
 
 		let call = pallet_utility::Call::<Runtime>::batch_all { calls };
+		let (total, _) = Self::bond_of(handle, &origin)?;
+		handle.record_log_costs_manual(2, 32)?;
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin.clone()).into(), call)?;
+
+		let (bonded, _) = Self::bond_of(handle, &origin)?;
+		let (address, staker) = (handle.context().address, handle.context().caller);
+		log_bonded(address, staker, bonded.saturating_sub(total).into()).record(handle)?;
+		Ok(())
+	}
+
+	#[precompile::public("addDelegation(address,uint8[],uint256)")]
+	#[precompile::public("add_delegation(address,uint8[],uint256)")]
+	fn add_delegation(
+		handle: &mut impl PrecompileHandle,
+		delegate: Address,
+		operations: Vec<u8>,
+		expiry: U256,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let delegate: pallet_staking::AccountIdLookupOf<Runtime> =
+			Runtime::AddressMapping::into_account_id(delegate.0).into();
+		let operations = operations
+			.into_iter()
+			.map(Self::u8_to_operation)
+			.collect::<MayRevert<Vec<_>>>()
+			.and_then(|operations| {
+				DelegatedOperations::try_from(operations)
+					.map_err(|_| RevertReason::custom("at most 3 operations").into())
+			})
+			.in_field("operations")?;
+		let expiry: u32 = expiry
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("block number").in_field("expiry"))?;
+		// An expiry of zero means the delegation never expires.
+		let expiry = (expiry != 0).then(|| BlockNumberFor::<Runtime>::from(expiry));
+		let call = pallet_staking::Call::<Runtime>::add_delegation { delegate, operations, expiry };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
+		Ok(())
+	}
+
+	#[precompile::public("removeDelegation(address)")]
+	#[precompile::public("remove_delegation(address)")]
+	fn remove_delegation(handle: &mut impl PrecompileHandle, delegate: Address) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let delegate: pallet_staking::AccountIdLookupOf<Runtime> =
+			Runtime::AddressMapping::into_account_id(delegate.0).into();
+		let call = pallet_staking::Call::<Runtime>::remove_delegation { delegate };
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
 		Ok(())
 	}
 
+	/// The account `caller` should dispatch as for `operations`.
+	///
+	/// Calls that don't go through the staking pallet (e.g. `set_keys`) can't see delegations, so
+	/// a delegate is checked here and then acts as the controller of the stash that granted it.
+	fn operating_account(
+		handle: &mut impl PrecompileHandle,
+		caller: Runtime::AccountId,
+		operations: &[DelegatedOperation],
+	) -> EvmResult<Runtime::AccountId> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		if pallet_staking::Pallet::<Runtime>::delegations(&caller).is_none() {
+			return Ok(caller);
+		}
+
+		let mut stash = None;
+		for operation in operations {
+			handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+			let ledger = pallet_staking::Pallet::<Runtime>::delegated_ledger(&caller, *operation)
+				.map_err(|_| revert("delegation does not allow this operation"))?;
+			stash = Some(ledger.stash);
+		}
+		stash
+			.and_then(|stash| pallet_staking::Pallet::<Runtime>::bonded(&stash))
+			.ok_or_else(|| revert("delegation does not allow this operation"))
+	}
+
+	/// The total and active bond of the ledger of `controller`.
+	fn bond_of(
+		handle: &mut impl PrecompileHandle,
+		controller: &Runtime::AccountId,
+	) -> EvmResult<(BalanceOf<Runtime>, BalanceOf<Runtime>)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(pallet_staking::Pallet::<Runtime>::ledger(controller)
+			.map(|ledger| (ledger.total, ledger.active))
+			.unwrap_or_default())
+	}
+
+	fn u8_to_operation(value: u8) -> MayRevert<DelegatedOperation> {
+		match value {
+			0 => Ok(DelegatedOperation::Validate),
+			1 => Ok(DelegatedOperation::SessionKeys),
+			2 => Ok(DelegatedOperation::Payout),
+			_ => Err(RevertReason::custom("unknown delegated operation").into()),
+		}
+	}
+
+	fn operation_to_u8(operation: DelegatedOperation) -> u8 {
+		match operation {
+			DelegatedOperation::Validate => 0,
+			DelegatedOperation::SessionKeys => 1,
+			DelegatedOperation::Payout => 2,
+		}
+	}
+
+	/// The rewards destination of a `payment_destination` code: 0 staked, 1 stash, 4 auto
+	/// compounding.
+	fn reward_destination(payee: u8) -> MayRevert<RewardDestination<Runtime::AccountId>> {
+		match payee {
+			0 => Ok(RewardDestination::Staked),
+			1 => Ok(RewardDestination::Stash),
+			PAYEE_AUTO_COMPOUND => Ok(RewardDestination::AutoCompound),
+			_ => Err(RevertReason::custom("unknown rewards destination").into()),
+		}
+	}
+
 	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime>> {
 		value
 			.try_into()
@@ -0,0 +1,28 @@
+//! Runtime API definition for the provider pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_provider::types::{DeviceId, DeviceInfo, ProviderId, ProviderInfo};
+use sp_std::vec::Vec;
+
+/// The maximum number of providers returned at once.
+pub const MAX_PAGE_SIZE: u32 = 256;
+
+sp_api::decl_runtime_apis! {
+	pub trait ProviderApi<AccountId, Balance, BlockNumber>
+		where
+			AccountId: Codec,
+			Balance: Codec,
+			BlockNumber: Codec,
+	{
+		/// A provider by id.
+		fn provider(pid: ProviderId) -> Option<ProviderInfo<AccountId, Balance>>;
+		/// The id of the provider registered by `owner`.
+		fn provider_of(owner: AccountId) -> Option<ProviderId>;
+		/// Up to `count` providers, capped at `MAX_PAGE_SIZE`, with ids from `start` on.
+		fn providers(start: ProviderId, count: u32) -> Vec<(ProviderId, ProviderInfo<AccountId, Balance>)>;
+		/// The devices of a provider.
+		fn devices(pid: ProviderId) -> Vec<(DeviceId, DeviceInfo<Balance, BlockNumber>)>;
+	}
+}
@@ -0,0 +1,566 @@
+//! Registry of compute providers.
+//!
+//! An account registers as a provider, then registers its devices. Every device requires
+//! `PledgePerDevice` of veTAKER to be pledged, that is reserved, by the provider. Providers are
+//! granted rewards, which they claim from the pallet account, and punished by slashing their
+//! pledge.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod types;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::types::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement::AllowDeath, OnUnbalanced, ReservableCurrency},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+	use sp_std::vec::Vec;
+
+	pub type BalanceOf<T> = <<T as Config>::PledgeCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::PledgeCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type ProviderInfoOf<T> = ProviderInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+	pub type DeviceInfoOf<T> = DeviceInfo<BalanceOf<T>, BlockNumberFor<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The currency pledged by providers, veTAKER.
+		type PledgeCurrency: ReservableCurrency<Self::AccountId>;
+		/// The currency rewards are paid in.
+		type RewardCurrency: Currency<Self::AccountId, Balance = BalanceOf<Self>>;
+		/// The pallet id, used to derive the account rewards are paid from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The pledge required by every device.
+		#[pallet::constant]
+		type PledgePerDevice: Get<BalanceOf<Self>>;
+		/// The maximum number of devices of a provider.
+		#[pallet::constant]
+		type MaxDevices: Get<u32>;
+		/// The origin which can reward providers.
+		type RewardOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The origin which can punish providers.
+		type PunishOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Handler for the pledge slashed when punishing a provider.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The id of the next registered provider.
+	#[pallet::storage]
+	#[pallet::getter(fn next_provider_id)]
+	pub type NextProviderId<T: Config> = StorageValue<_, ProviderId, ValueQuery>;
+
+	/// The registered providers.
+	#[pallet::storage]
+	#[pallet::getter(fn providers)]
+	pub type Providers<T: Config> =
+		StorageMap<_, Twox64Concat, ProviderId, ProviderInfoOf<T>, OptionQuery>;
+
+	/// The provider registered by an account.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_of)]
+	pub type ProviderOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ProviderId, OptionQuery>;
+
+	/// The devices of every provider.
+	#[pallet::storage]
+	#[pallet::getter(fn devices)]
+	pub type Devices<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProviderId,
+		Blake2_128Concat,
+		DeviceId,
+		DeviceInfoOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A provider was registered.
+		Registered { pid: ProviderId, owner: T::AccountId },
+		/// A provider was deregistered, and its pledge released.
+		Deregistered { pid: ProviderId },
+		/// A provider pledged more veTAKER.
+		Pledged { pid: ProviderId, amount: BalanceOf<T> },
+		/// A provider released part of its pledge.
+		Unpledged { pid: ProviderId, amount: BalanceOf<T> },
+		/// A provider registered a device.
+		DeviceAdded { pid: ProviderId, device: DeviceId },
+		/// A provider removed a device.
+		DeviceRemoved { pid: ProviderId, device: DeviceId },
+		/// A provider was granted rewards.
+		Rewarded { pid: ProviderId, amount: BalanceOf<T> },
+		/// A provider claimed its rewards.
+		RewardsClaimed { pid: ProviderId, amount: BalanceOf<T> },
+		/// A provider was punished, and `amount` of its pledge slashed.
+		Punished { pid: ProviderId, amount: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already registered a provider.
+		AlreadyRegistered,
+		/// The account has not registered a provider.
+		NotRegistered,
+		/// No provider with this id.
+		ProviderNotFound,
+		/// The device is already registered by the provider.
+		DeviceAlreadyRegistered,
+		/// The device is not registered by the provider.
+		DeviceNotFound,
+		/// The provider has `MaxDevices` devices already.
+		TooManyDevices,
+		/// The pledge does not cover the devices of the provider.
+		InsufficientPledge,
+		/// Devices must be removed before deregistering.
+		HasDevices,
+		/// Rewards must be claimed before deregistering.
+		HasUnpaidRewards,
+		/// There are no rewards to claim.
+		NothingToClaim,
+		/// The amount is zero.
+		ZeroAmount,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the origin as a provider.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 3))]
+		pub fn register(origin: OriginFor<T>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!ProviderOf::<T>::contains_key(&owner), Error::<T>::AlreadyRegistered);
+
+			let pid = NextProviderId::<T>::mutate(|next| {
+				let pid = *next;
+				*next = next.saturating_add(1);
+				pid
+			});
+			Providers::<T>::insert(
+				pid,
+				ProviderInfo {
+					owner: owner.clone(),
+					cap_pledge: Zero::zero(),
+					total_pledge: Zero::zero(),
+					devices_num: 0,
+					total_punishment: Zero::zero(),
+					total_rewards: Zero::zero(),
+					unpaid_rewards: Zero::zero(),
+				},
+			);
+			ProviderOf::<T>::insert(&owner, pid);
+
+			Self::deposit_event(Event::Registered { pid, owner });
+			Ok(())
+		}
+
+		/// Deregister the provider of the origin, releasing its pledge.
+		///
+		/// All devices must be removed and rewards claimed first.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn deregister(origin: OriginFor<T>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let (pid, provider) = Self::provider_by_owner(&owner)?;
+			ensure!(provider.devices_num == 0, Error::<T>::HasDevices);
+			ensure!(provider.unpaid_rewards.is_zero(), Error::<T>::HasUnpaidRewards);
+
+			T::PledgeCurrency::unreserve(&owner, provider.total_pledge);
+			Providers::<T>::remove(pid);
+			ProviderOf::<T>::remove(&owner);
+
+			Self::deposit_event(Event::Deregistered { pid });
+			Ok(())
+		}
+
+		/// Pledge `amount` more veTAKER.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn pledge(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let (pid, mut provider) = Self::provider_by_owner(&owner)?;
+
+			T::PledgeCurrency::reserve(&owner, amount)?;
+			provider.total_pledge = provider.total_pledge.saturating_add(amount);
+			Providers::<T>::insert(pid, provider);
+
+			Self::deposit_event(Event::Pledged { pid, amount });
+			Ok(())
+		}
+
+		/// Release `amount` of the pledge. The rest must still cover the devices.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn unpledge(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let (pid, mut provider) = Self::provider_by_owner(&owner)?;
+			ensure!(
+				provider.total_pledge >= provider.cap_pledge.saturating_add(amount),
+				Error::<T>::InsufficientPledge
+			);
+
+			T::PledgeCurrency::unreserve(&owner, amount);
+			provider.total_pledge = provider.total_pledge.saturating_sub(amount);
+			Providers::<T>::insert(pid, provider);
+
+			Self::deposit_event(Event::Unpledged { pid, amount });
+			Ok(())
+		}
+
+		/// Register a device. The pledge must cover it.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn add_device(origin: OriginFor<T>, device: DeviceId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let (pid, mut provider) = Self::provider_by_owner(&owner)?;
+			ensure!(!Devices::<T>::contains_key(pid, device), Error::<T>::DeviceAlreadyRegistered);
+			ensure!(provider.devices_num < T::MaxDevices::get(), Error::<T>::TooManyDevices);
+
+			let pledge = T::PledgePerDevice::get();
+			let cap_pledge = provider.cap_pledge.saturating_add(pledge);
+			ensure!(provider.total_pledge >= cap_pledge, Error::<T>::InsufficientPledge);
+
+			provider.cap_pledge = cap_pledge;
+			provider.devices_num += 1;
+			Providers::<T>::insert(pid, provider);
+			Devices::<T>::insert(
+				pid,
+				device,
+				DeviceInfo { pledge, registered_at: frame_system::Pallet::<T>::block_number() },
+			);
+
+			Self::deposit_event(Event::DeviceAdded { pid, device });
+			Ok(())
+		}
+
+		/// Remove a device, freeing the pledge it required.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn remove_device(origin: OriginFor<T>, device: DeviceId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let (pid, mut provider) = Self::provider_by_owner(&owner)?;
+			let info = Devices::<T>::take(pid, device).ok_or(Error::<T>::DeviceNotFound)?;
+
+			provider.cap_pledge = provider.cap_pledge.saturating_sub(info.pledge);
+			provider.devices_num = provider.devices_num.saturating_sub(1);
+			Providers::<T>::insert(pid, provider);
+
+			Self::deposit_event(Event::DeviceRemoved { pid, device });
+			Ok(())
+		}
+
+		/// Pay the unpaid rewards of the provider of the origin.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let (pid, mut provider) = Self::provider_by_owner(&owner)?;
+			let amount = provider.unpaid_rewards;
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			T::RewardCurrency::transfer(&Self::account_id(), &owner, amount, AllowDeath)?;
+			provider.unpaid_rewards = Zero::zero();
+			Providers::<T>::insert(pid, provider);
+
+			Self::deposit_event(Event::RewardsClaimed { pid, amount });
+			Ok(())
+		}
+
+		/// Grant `amount` of rewards to a provider, paid from the pallet account when claimed.
+		///
+		/// The dispatch origin for this call must be `RewardOrigin`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn reward(
+			origin: OriginFor<T>,
+			pid: ProviderId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::RewardOrigin::ensure_origin(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			Providers::<T>::try_mutate(pid, |provider| -> DispatchResult {
+				let provider = provider.as_mut().ok_or(Error::<T>::ProviderNotFound)?;
+				provider.total_rewards = provider.total_rewards.saturating_add(amount);
+				provider.unpaid_rewards = provider.unpaid_rewards.saturating_add(amount);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Rewarded { pid, amount });
+			Ok(())
+		}
+
+		/// Slash up to `amount` of the pledge of a provider.
+		///
+		/// The dispatch origin for this call must be `PunishOrigin`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn punish(
+			origin: OriginFor<T>,
+			pid: ProviderId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::PunishOrigin::ensure_origin(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let mut provider = Providers::<T>::get(pid).ok_or(Error::<T>::ProviderNotFound)?;
+
+			let amount = amount.min(provider.total_pledge);
+			let (imbalance, unslashed) = T::PledgeCurrency::slash_reserved(&provider.owner, amount);
+			let slashed = amount.saturating_sub(unslashed);
+			T::Slash::on_unbalanced(imbalance);
+
+			provider.total_pledge = provider.total_pledge.saturating_sub(slashed);
+			provider.total_punishment = provider.total_punishment.saturating_add(slashed);
+			Providers::<T>::insert(pid, provider);
+
+			Self::deposit_event(Event::Punished { pid, amount: slashed });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account rewards are paid from.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		fn provider_by_owner(
+			owner: &T::AccountId,
+		) -> Result<(ProviderId, ProviderInfoOf<T>), DispatchError> {
+			let pid = ProviderOf::<T>::get(owner).ok_or(Error::<T>::NotRegistered)?;
+			let provider = Providers::<T>::get(pid).ok_or(Error::<T>::ProviderNotFound)?;
+			Ok((pid, provider))
+		}
+
+		/// The devices of a provider.
+		pub fn devices_of(pid: ProviderId) -> Vec<(DeviceId, DeviceInfoOf<T>)> {
+			Devices::<T>::iter_prefix(pid).collect()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate as pallet_provider;
+	use crate::types::DeviceId;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl, parameter_types,
+		traits::{ConstU32, ConstU64, Currency, ReservableCurrency},
+		PalletId,
+	};
+	use sp_runtime::{BuildStorage, DispatchError};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			Provider: pallet_provider,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	parameter_types! {
+		pub const ProviderPalletId: PalletId = PalletId(*b"py/prvdr");
+	}
+
+	impl Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type PledgeCurrency = Balances;
+		type RewardCurrency = Balances;
+		type PalletId = ProviderPalletId;
+		type PledgePerDevice = ConstU64<100>;
+		type MaxDevices = ConstU32<2>;
+		type RewardOrigin = frame_system::EnsureRoot<u64>;
+		type PunishOrigin = frame_system::EnsureRoot<u64>;
+		type Slash = ();
+	}
+
+	const OWNER: u64 = 1;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(OWNER, 1_000), (2, 1_000), (Provider::account_id(), 1_000)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn device(id: u8) -> DeviceId {
+		[id; 32]
+	}
+
+	fn provider() -> ProviderInfoOf<Test> {
+		Provider::providers(0).unwrap()
+	}
+
+	#[test]
+	fn register_and_deregister() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Provider::register(RuntimeOrigin::signed(OWNER)));
+			assert_noop!(
+				Provider::register(RuntimeOrigin::signed(OWNER)),
+				Error::<Test>::AlreadyRegistered
+			);
+			assert_eq!(Provider::provider_of(OWNER), Some(0));
+			assert_ok!(Provider::register(RuntimeOrigin::signed(2)));
+			assert_eq!(Provider::provider_of(2), Some(1));
+
+			assert_ok!(Provider::pledge(RuntimeOrigin::signed(OWNER), 300));
+			assert_eq!(Balances::reserved_balance(OWNER), 300);
+
+			assert_ok!(Provider::deregister(RuntimeOrigin::signed(OWNER)));
+			assert_eq!(Balances::reserved_balance(OWNER), 0);
+			assert_eq!(Provider::provider_of(OWNER), None);
+			assert_eq!(Provider::providers(0), None);
+			assert_noop!(
+				Provider::deregister(RuntimeOrigin::signed(OWNER)),
+				Error::<Test>::NotRegistered
+			);
+		});
+	}
+
+	#[test]
+	fn devices_must_be_covered_by_the_pledge() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Provider::register(RuntimeOrigin::signed(OWNER)));
+			assert_noop!(
+				Provider::add_device(RuntimeOrigin::signed(OWNER), device(1)),
+				Error::<Test>::InsufficientPledge
+			);
+
+			assert_ok!(Provider::pledge(RuntimeOrigin::signed(OWNER), 250));
+			assert_ok!(Provider::add_device(RuntimeOrigin::signed(OWNER), device(1)));
+			assert_noop!(
+				Provider::add_device(RuntimeOrigin::signed(OWNER), device(1)),
+				Error::<Test>::DeviceAlreadyRegistered
+			);
+			assert_ok!(Provider::add_device(RuntimeOrigin::signed(OWNER), device(2)));
+			assert_eq!((provider().cap_pledge, provider().devices_num), (200, 2));
+			assert_eq!(Provider::devices_of(0).len(), 2);
+
+			assert_ok!(Provider::pledge(RuntimeOrigin::signed(OWNER), 100));
+			assert_noop!(
+				Provider::add_device(RuntimeOrigin::signed(OWNER), device(3)),
+				Error::<Test>::TooManyDevices
+			);
+
+			// the pledge left must cover both devices.
+			assert_noop!(
+				Provider::unpledge(RuntimeOrigin::signed(OWNER), 151),
+				Error::<Test>::InsufficientPledge
+			);
+			assert_ok!(Provider::unpledge(RuntimeOrigin::signed(OWNER), 150));
+			assert_eq!(Balances::reserved_balance(OWNER), 200);
+
+			assert_noop!(
+				Provider::deregister(RuntimeOrigin::signed(OWNER)),
+				Error::<Test>::HasDevices
+			);
+			assert_ok!(Provider::remove_device(RuntimeOrigin::signed(OWNER), device(1)));
+			assert_noop!(
+				Provider::remove_device(RuntimeOrigin::signed(OWNER), device(1)),
+				Error::<Test>::DeviceNotFound
+			);
+			assert_eq!((provider().cap_pledge, provider().devices_num), (100, 1));
+			assert_ok!(Provider::unpledge(RuntimeOrigin::signed(OWNER), 100));
+		});
+	}
+
+	#[test]
+	fn rewards_are_claimed_from_the_pallet_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Provider::register(RuntimeOrigin::signed(OWNER)));
+			assert_noop!(
+				Provider::reward(RuntimeOrigin::signed(OWNER), 0, 100),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Provider::reward(RuntimeOrigin::root(), 1, 100),
+				Error::<Test>::ProviderNotFound
+			);
+			assert_noop!(
+				Provider::claim_rewards(RuntimeOrigin::signed(OWNER)),
+				Error::<Test>::NothingToClaim
+			);
+
+			assert_ok!(Provider::reward(RuntimeOrigin::root(), 0, 100));
+			assert_ok!(Provider::reward(RuntimeOrigin::root(), 0, 50));
+			assert_eq!((provider().total_rewards, provider().unpaid_rewards), (150, 150));
+			assert_noop!(
+				Provider::deregister(RuntimeOrigin::signed(OWNER)),
+				Error::<Test>::HasUnpaidRewards
+			);
+
+			assert_ok!(Provider::claim_rewards(RuntimeOrigin::signed(OWNER)));
+			assert_eq!(Balances::free_balance(OWNER), 1_150);
+			assert_eq!(Balances::free_balance(Provider::account_id()), 850);
+			assert_eq!((provider().total_rewards, provider().unpaid_rewards), (150, 0));
+			System::assert_last_event(Event::RewardsClaimed { pid: 0, amount: 150 }.into());
+			assert_ok!(Provider::deregister(RuntimeOrigin::signed(OWNER)));
+		});
+	}
+
+	#[test]
+	fn punishment_slashes_at_most_the_pledge() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Provider::register(RuntimeOrigin::signed(OWNER)));
+			assert_ok!(Provider::pledge(RuntimeOrigin::signed(OWNER), 100));
+			assert_noop!(
+				Provider::punish(RuntimeOrigin::signed(2), 0, 10),
+				DispatchError::BadOrigin
+			);
+			let issuance = Balances::total_issuance();
+
+			assert_ok!(Provider::punish(RuntimeOrigin::root(), 0, 40));
+			assert_eq!((provider().total_pledge, provider().total_punishment), (60, 40));
+			assert_ok!(Provider::punish(RuntimeOrigin::root(), 0, 1_000));
+			assert_eq!((provider().total_pledge, provider().total_punishment), (0, 100));
+			System::assert_last_event(Event::Punished { pid: 0, amount: 60 }.into());
+
+			assert_eq!(Balances::reserved_balance(OWNER), 0);
+			assert_eq!(Balances::total_issuance(), issuance - 100);
+		});
+	}
+}
@@ -0,0 +1,37 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The identifier of a provider, assigned on registration.
+pub type ProviderId = u64;
+
+/// The identifier of a device, chosen by its provider.
+pub type DeviceId = [u8; 32];
+
+/// A compute provider.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ProviderInfo<AccountId, Balance> {
+	/// The account which registered the provider and manages it.
+	pub owner: AccountId,
+	/// The pledge required by the registered devices.
+	pub cap_pledge: Balance,
+	/// The veTAKER reserved by the owner. Never below `cap_pledge`, unless punished.
+	pub total_pledge: Balance,
+	/// The number of registered devices.
+	pub devices_num: u32,
+	/// The pledge slashed over the lifetime of the provider.
+	pub total_punishment: Balance,
+	/// The rewards granted over the lifetime of the provider.
+	pub total_rewards: Balance,
+	/// The rewards granted but not claimed yet.
+	pub unpaid_rewards: Balance,
+}
+
+/// A device registered by a provider.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DeviceInfo<Balance, BlockNumber> {
+	/// The pledge the device adds to its provider's `cap_pledge`.
+	pub pledge: Balance,
+	/// The block the device was registered at.
+	pub registered_at: BlockNumber,
+}
@@ -22,7 +22,7 @@
 use codec::Codec;
 use codec::{Decode, Encode};
 pub use fp_account::AccountId20;
-use pallet_staking::RewardDestination;
+use pallet_staking::{CompoundPolicy, RewardDestination};
 use scale_info::TypeInfo;
 use sp_runtime::{Perbill, RuntimeDebug};
 use sp_std::vec::Vec;
@@ -44,6 +44,28 @@ pub struct NominatorInfo {
 	pub total_staking: u128,
 	pub active_staking: u128,
 	pub rewrds_destination: RewardDestination<AccountId20>,
+	pub auto_compound: Option<CompoundPolicy<u128>>,
+}
+/// `NominatorInfo` as returned before version 2 of `StakingRpcApi`.
+#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct NominatorInfoV1 {
+	pub stash_account: AccountId20,
+	pub target_validators: Vec<AccountId20>,
+	pub total_staking: u128,
+	pub active_staking: u128,
+	pub rewrds_destination: RewardDestination<AccountId20>,
+}
+impl From<NominatorInfoV1> for NominatorInfo {
+	fn from(info: NominatorInfoV1) -> Self {
+		NominatorInfo {
+			stash_account: info.stash_account,
+			target_validators: info.target_validators,
+			total_staking: info.total_staking,
+			active_staking: info.active_staking,
+			rewrds_destination: info.rewrds_destination,
+			auto_compound: None,
+		}
+	}
 }
 #[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
 pub struct ValidatorInfo {
@@ -55,12 +77,119 @@ pub struct ValidatorInfo {
 	pub commission: Perbill,
 	pub can_nominated: bool,
 }
+/// What an account earned in one era, and what happened to it on payout.
+#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct EraReward {
+	pub era: u32,
+	pub earned: u128,
+	pub commission: u128,
+	pub staking: u128,
+	pub paid: u128,
+	pub destination: Option<RewardDestination<AccountId20>>,
+}
+/// Reward totals of an account, with the per-era history for a range of eras.
+#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct AccountRewards {
+	pub total_rewards: u128,
+	pub paid_rewards: u128,
+	pub eras: Vec<EraReward>,
+}
+/// The staking events a client can subscribe to.
+#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub enum StakingEvent {
+	EraPayout { era: u32, validator_payout: u128, remainder: u128 },
+	EraReward {
+		era: u32,
+		validator: AccountId20,
+		account: AccountId20,
+		commission: u128,
+		staking: u128,
+	},
+	Slashed { staker: AccountId20, amount: u128 },
+	StakersElected,
+}
+/// The largest page the paginated staking APIs return.
+pub const MAX_PAGE_SIZE: u32 = 256;
+
+/// The order in which validators are listed.
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub enum ValidatorOrder {
+	/// By bonded stake, highest first, as kept by the voter list.
+	Stake,
+	/// In storage order, as kept by the target list.
+	Storage,
+}
+/// One page of a paginated staking list.
+///
+/// `next` is the last account of this page, to be passed as the start of the next page, or
+/// `None` if this is the last page.
+#[derive(Encode, Decode, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct StakingPage<T> {
+	pub items: Vec<T>,
+	pub next: Option<AccountId20>,
+}
+
+/// Why a page of a paginated staking list could not be returned.
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub enum PageError {
+	/// The start of the page is not in the list, e.g. it left the list since the previous page.
+	StartNotFound,
+}
+
+/// Collect up to `page_size` (at most `MAX_PAGE_SIZE`) items from `accounts`, skipping the
+/// accounts `info` has nothing for.
+pub fn paginate<T>(
+	accounts: impl Iterator<Item = AccountId20>,
+	page_size: u32,
+	info: impl Fn(&AccountId20) -> Option<T>,
+) -> StakingPage<T> {
+	let page_size = page_size.min(MAX_PAGE_SIZE) as usize;
+	let mut accounts = accounts.filter_map(|acc| info(&acc).map(|item| (acc, item))).peekable();
+	let mut items = Vec::new();
+	let mut last = None;
+	while items.len() < page_size {
+		match accounts.next() {
+			Some((acc, item)) => {
+				last = Some(acc);
+				items.push(item);
+			},
+			None => break,
+		}
+	}
+	let next = if accounts.peek().is_some() { last } else { None };
+	StakingPage { items, next }
+}
+
 sp_api::decl_runtime_apis! {
+	/// Version 2 added the compounding policy to `NominatorInfo`, version 3 `reward_history`,
+	/// version 4 `staking_events` and version 5 `validators` and `nominators`. Callers check the
+	/// version of a block before calling the methods it may not have.
+	#[api_version(5)]
 	pub trait StakingRpcApi {
-		 fn nominator_info(account: &AccountId20) -> Option<NominatorInfo>;
+		#[changed_in(2)]
+		fn nominator_info(account: &AccountId20) -> Option<NominatorInfoV1>;
+		fn nominator_info(account: &AccountId20) -> Option<NominatorInfo>;
 		 fn validator_info(account: &AccountId20) -> Option<ValidatorInfo>;
 		fn get_validator_rewards(account: &AccountId20, era_index: u32) -> Option<u128>;
 		fn get_nominator_rewards(account: &AccountId20, era_index: u32) -> Option<u128>;
 		fn all_validators_can_nominate() -> Vec<AccountId20>;
+		/// The reward totals of `account` and its records from `from_era` to `to_era`, of which
+		/// only the last `HistoryDepth` eras are retained.
+		fn reward_history(account: &AccountId20, from_era: u32, to_era: u32) -> AccountRewards;
+		/// The staking events deposited in the block.
+		fn staking_events() -> Vec<StakingEvent>;
+		/// A page of validators, starting after `start`.
+		fn validators(
+			start: Option<AccountId20>,
+			page_size: u32,
+			order: ValidatorOrder,
+		) -> Result<StakingPage<ValidatorInfo>, PageError>;
+		/// A page of nominators, highest stake first, starting after `start`. Only the nominators
+		/// of `validator` are listed if it is given.
+		fn nominators(
+			validator: Option<AccountId20>,
+			start: Option<AccountId20>,
+			page_size: u32,
+		) -> Result<StakingPage<NominatorInfo>, PageError>;
 	}
 }
@@ -302,7 +302,7 @@ pub mod pallet;
 
 use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
 use frame_support::{
-	traits::{Currency, Defensive, Get},
+	traits::{ConstU32, Currency, Defensive, Get},
 	weights::Weight,
 	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
@@ -397,6 +397,10 @@ pub enum RewardDestination<AccountId> {
 	Account(AccountId),
 	/// Receive no reward.
 	None,
+	/// Pay into the stash account, staking only part of it according to the stash's
+	/// `CompoundPolicy`. Unpaid rewards are paid out automatically at the end of each era once
+	/// they reach the policy threshold.
+	AutoCompound,
 }
 
 impl<AccountId> Default for RewardDestination<AccountId> {
@@ -418,6 +422,77 @@ pub struct ValidatorPrefs {
 	pub blocked: bool,
 }
 
+/// An operation a stash may delegate to an account other than its controller.
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DelegatedOperation {
+	/// Call `validate` on behalf of the stash.
+	Validate,
+	/// Rotate the session keys of the stash and `chill` it.
+	SessionKeys,
+	/// Call `payout_stakers` for the stash.
+	Payout,
+}
+
+/// The operations of a delegation, each at most once.
+pub type DelegatedOperations = BoundedVec<DelegatedOperation, ConstU32<3>>;
+
+/// A scoped delegation granted by a stash to another account.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Delegation<AccountId, BlockNumber> {
+	/// The stash that granted the delegation.
+	pub stash: AccountId,
+	/// The operations the delegate is allowed to perform.
+	pub operations: DelegatedOperations,
+	/// The block at which the delegation stops being valid, if any.
+	pub expiry: Option<BlockNumber>,
+}
+
+impl<AccountId, BlockNumber: PartialOrd> Delegation<AccountId, BlockNumber> {
+	/// Whether this delegation allows `operation` at block `now`.
+	pub fn allows(&self, operation: DelegatedOperation, now: BlockNumber) -> bool {
+		self.operations.contains(&operation) && self.expiry.as_ref().map_or(true, |e| now < *e)
+	}
+}
+
+/// How rewards of a stash with `RewardDestination::AutoCompound` are compounded.
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CompoundPolicy<Balance> {
+	/// Unpaid rewards are only paid out automatically once they reach this amount.
+	pub threshold: Balance,
+	/// The part of each payout that is added to the stake. The rest stays free in the stash.
+	pub stake: Perbill,
+}
+
+impl<Balance: Zero> Default for CompoundPolicy<Balance> {
+	fn default() -> Self {
+		CompoundPolicy { threshold: Zero::zero(), stake: Perbill::one() }
+	}
+}
+
+/// What an account earned in one era, and what happened to it on payout.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EraRewardRecord<AccountId, Balance> {
+	/// Commission earned as a validator.
+	pub commission: Balance,
+	/// Reward earned for the stake, as a validator or a nominator.
+	pub staking: Balance,
+	/// Amount actually paid out. Zero until the era is claimed.
+	pub paid: Balance,
+	/// Where the payout went, once claimed.
+	pub destination: Option<RewardDestination<AccountId>>,
+}
+
+impl<AccountId, Balance: Zero> Default for EraRewardRecord<AccountId, Balance> {
+	fn default() -> Self {
+		EraRewardRecord {
+			commission: Zero::zero(),
+			staking: Zero::zero(),
+			paid: Zero::zero(),
+			destination: None,
+		}
+	}
+}
+
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
@@ -49,6 +49,119 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+pub mod v15 {
+	use super::*;
+
+	/// Backfill the total of `RewardsInfoForAccount`.
+	///
+	/// Totals only count rewards earned since v15. Before it, the total was not kept up to date,
+	/// so it is set to at least the rewards paid and still unpaid at the upgrade. Unpaid rewards
+	/// that already expired are not included.
+	pub struct MigrateToV15<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV15<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 14,
+				"Required v14 before upgrading to v15"
+			);
+
+			Ok(Default::default())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let current = STORAGE_VERSION;
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current == 15 && onchain == 14 {
+				let mut translated = 0u64;
+				RewardsInfoForAccount::<T>::translate_values::<
+					(BalanceOf<T>, BalanceOf<T>, Vec<(EraIndex, BalanceOf<T>)>),
+					_,
+				>(|(total, paid, unpaid)| {
+					translated += 1;
+					let earned = unpaid
+						.iter()
+						.fold(paid, |earned, (_, reward)| earned.saturating_add(*reward));
+					Some((total.max(earned), paid, unpaid))
+				});
+				current.put::<Pallet<T>>();
+
+				log!(info, "v15 applied successfully, {} reward totals backfilled", translated);
+				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+			} else {
+				log!(warn, "Skipping v15, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 15,
+				"v15 not applied"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v14 {
+	use super::*;
+
+	/// Populate `VoterList` from the existing `Nominators` and `Validators`.
+	///
+	/// Needed when switching `VoterList` from `UseNominatorsAndValidatorsMap` to a bags-list, since
+	/// the new list starts out empty.
+	pub struct MigrateToV14<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV14<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 13,
+				"Required v13 before upgrading to v14"
+			);
+
+			let voters = Nominators::<T>::count() + Validators::<T>::count();
+			Ok(voters.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 13 {
+				let voters = Nominators::<T>::iter_keys().chain(Validators::<T>::iter_keys());
+				let inserted = T::VoterList::unsafe_regenerate(voters, Pallet::<T>::weight_of_fn());
+				frame_support::traits::StorageVersion::new(14).put::<Pallet<T>>();
+
+				log!(info, "v14 applied successfully, {} voters inserted into VoterList", inserted);
+				T::BlockWeights::get().max_block
+			} else {
+				log!(warn, "Skipping v14, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let voters: u32 = Decode::decode(&mut state.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			frame_support::ensure!(
+				T::VoterList::count() == voters,
+				"not all voters were inserted into VoterList"
+			);
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 14,
+				"v14 not applied"
+			);
+
+			Ok(())
+		}
+	}
+}
+
 pub mod v13 {
 	use super::*;
 
@@ -49,9 +49,9 @@ use sp_std::prelude::*;
 use super::{pallet::*, STAKING_ID};
 use crate::{
 	election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
-	BalanceOf, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure, MaxWinnersOf,
-	Nominations, PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger,
-	ValidatorPrefs,
+	BalanceOf, CompoundPolicy, DelegatedOperation, EraPayout, Exposure, ExposureOf, Forcing,
+	IndividualExposure, MaxWinnersOf, Nominations, PositiveImbalanceOf, RewardDestination,
+	SessionInterface, StakingLedger, ValidatorPrefs,
 };
 
 /// The maximum number of iterations that we do whilst iterating over `T::VoterList` in
@@ -172,6 +172,10 @@ impl<T: Config> Pallet<T> {
 			}
 			rewards_info.1 += payout_amount;
 			rewards_info.2.remove(index);
+			<RewardHistory<T>>::mutate(era, &stash, |record| {
+				record.paid = record.paid.saturating_add(payout_amount);
+				record.destination = Some(Self::payee(&stash));
+			});
 			T::Reward::on_unbalanced(total_imbalance);
 			<RewardsInfoForAccount<T>>::insert(&stash, rewards_info);
 		}
@@ -201,10 +205,46 @@ impl<T: Config> Pallet<T> {
 
 	/// Update the ledger for a controller.
 	///
-	/// This will also update the stash lock.
+	/// This will also update the stash lock, and the score of the stash in `VoterList` if it is
+	/// in there. Every change of active stake (bonding, unbonding, rewards, slashes) goes through
+	/// here, which keeps the list sorted.
 	pub(crate) fn update_ledger(controller: &T::AccountId, ledger: &StakingLedger<T>) {
 		T::Currency::set_lock(STAKING_ID, &ledger.stash, ledger.total, WithdrawReasons::all());
 		<Ledger<T>>::insert(controller, ledger);
+
+		// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+		if T::VoterList::contains(&ledger.stash) {
+			let _ = T::VoterList::on_update(&ledger.stash, Self::weight_of(&ledger.stash))
+				.defensive();
+		}
+	}
+
+	/// The ledger that `delegate` may operate on for `operation`, through a delegation granted by
+	/// its stash.
+	pub fn delegated_ledger(
+		delegate: &T::AccountId,
+		operation: DelegatedOperation,
+	) -> Result<StakingLedger<T>, DispatchError> {
+		let delegation = Self::delegations(delegate).ok_or(Error::<T>::NotDelegate)?;
+		ensure!(
+			delegation.allows(operation, frame_system::Pallet::<T>::block_number()),
+			Error::<T>::DelegationNotPermitted
+		);
+		let controller = Self::bonded(&delegation.stash).ok_or(Error::<T>::NotStash)?;
+		Self::ledger(&controller).ok_or_else(|| Error::<T>::NotController.into())
+	}
+
+	/// The ledger that `who` may operate on for `operation`, either as the controller or as a
+	/// delegate of the stash.
+	pub(crate) fn operating_ledger(
+		who: &T::AccountId,
+		operation: DelegatedOperation,
+	) -> Result<StakingLedger<T>, DispatchError> {
+		match Self::ledger(who) {
+			Some(ledger) => Ok(ledger),
+			None if Delegations::<T>::contains_key(who) => Self::delegated_ledger(who, operation),
+			None => Err(Error::<T>::NotController.into()),
+		}
 	}
 
 	/// Chill a stash account.
@@ -236,10 +276,90 @@ impl<T: Config> Pallet<T> {
 			RewardDestination::Account(dest_account) => {
 				Some(T::Currency::deposit_creating(&dest_account, amount))
 			},
+			RewardDestination::AutoCompound => Self::bonded(stash)
+				.and_then(|c| Self::ledger(&c).map(|l| (c, l)))
+				.and_then(|(controller, mut l)| {
+					let staked = Self::compound_policies(stash).unwrap_or_default().stake * amount;
+					l.active += staked;
+					l.total += staked;
+					let r = T::Currency::deposit_into_existing(stash, amount).ok();
+					Self::update_ledger(&controller, &l);
+					r
+				}),
 			RewardDestination::None => None,
 		}
 	}
 
+	/// Set the reward destination of `stash`, keeping `CompoundPolicies` in sync with it.
+	pub(crate) fn do_set_payee(stash: &T::AccountId, payee: RewardDestination<T::AccountId>) {
+		if payee == RewardDestination::AutoCompound {
+			if !<CompoundPolicies<T>>::contains_key(stash) {
+				<CompoundPolicies<T>>::insert(stash, CompoundPolicy::default());
+			}
+		} else {
+			<CompoundPolicies<T>>::remove(stash);
+		}
+		<Payee<T>>::insert(stash, payee);
+	}
+
+	/// Pay out the unpaid rewards of the auto-compounding stashes whose total reached the
+	/// threshold of their policy, resuming from `CompoundCursor`.
+	///
+	/// At most `MaxAutoCompoundsPerBlock` era rewards are paid out. A stash left with unpaid
+	/// eras is visited again in the next block, unless paying one of them failed, in which case
+	/// `AutoCompoundFailed` is emitted and the stash is left for the next era. Returns the weight
+	/// consumed.
+	pub(crate) fn auto_compound_rewards() -> Weight {
+		let Some(cursor) = <CompoundCursor<T>>::get() else { return T::DbWeight::get().reads(1) };
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		let mut budget = T::MaxAutoCompoundsPerBlock::get();
+		let mut policies = if cursor.is_empty() {
+			<CompoundPolicies<T>>::iter()
+		} else {
+			<CompoundPolicies<T>>::iter_from(cursor.clone())
+		};
+		let mut last_key = cursor;
+
+		while budget > 0 {
+			let Some((stash, policy)) = policies.next() else {
+				<CompoundCursor<T>>::kill();
+				return weight
+			};
+			weight.saturating_accrue(T::DbWeight::get().reads(2));
+
+			let unpaid = <RewardsInfoForAccount<T>>::get(&stash).2;
+			let total = unpaid
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |acc, (_, reward)| acc.saturating_add(*reward));
+			if !total.is_zero() && total >= policy.threshold {
+				let eras = unpaid.len() as u32;
+				let mut attempted = 0;
+				let mut failed = false;
+				for (era, _) in unpaid.into_iter().take(budget as usize) {
+					attempted += 1;
+					weight.saturating_accrue(T::WeightInfo::payout_stakers_alive_staked(1));
+					if let Err(e) = Self::do_payout_stakers(stash.clone(), era) {
+						log!(warn, "auto-compounding era {} of {:?} failed: {:?}", era, stash, e);
+						Self::deposit_event(Event::<T>::AutoCompoundFailed {
+							stash: stash.clone(),
+							era,
+						});
+						failed = true;
+						break
+					}
+				}
+				budget -= attempted;
+				if !failed && attempted < eras {
+					break
+				}
+			}
+			last_key = policies.last_raw_key().to_vec();
+		}
+
+		<CompoundCursor<T>>::put(last_key);
+		weight
+	}
+
 	/// Plan a new session potentially trigger a new era.
 	fn new_session(
 		session_index: SessionIndex,
@@ -437,7 +557,13 @@ impl<T: Config> Pallet<T> {
 						active_era.index,
 						validator_staking_payout + validator_commission_payout,
 					));
+					rewards_info.0 += validator_staking_payout + validator_commission_payout;
 					<RewardsInfoForAccount<T>>::insert(&validator, rewards_info);
+					<RewardHistory<T>>::mutate(active_era.index, &validator, |record| {
+						record.commission =
+							record.commission.saturating_add(validator_commission_payout);
+						record.staking = record.staking.saturating_add(validator_staking_payout);
+					});
 					// emit validator 'EraRewardInfoEx' event
 					Self::deposit_event(Event::<T>::EraRewardInfoEx(
 						active_era.index,
@@ -468,6 +594,10 @@ impl<T: Config> Pallet<T> {
 						} else {
 							rewards_info.2.push((active_era.index, nominator_reward));
 						}
+						rewards_info.0 += nominator_reward;
+						<RewardHistory<T>>::mutate(active_era.index, &nominator.who, |record| {
+							record.staking = record.staking.saturating_add(nominator_reward);
+						});
 						// emit nominator 'EraRewardInfoEx' event
 						Self::deposit_event(Event::<T>::EraRewardInfoEx(
 							active_era.index,
@@ -481,6 +611,12 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 
+			// Compound the rewards from the next `on_initialize` on, unless the previous era's
+			// stashes are still being compounded.
+			if !<CompoundCursor<T>>::exists() {
+				<CompoundCursor<T>>::put(Vec::<u8>::new());
+			}
+
 			// Clear offending validators.
 			<OffendingValidators<T>>::kill();
 		}
@@ -707,6 +843,7 @@ impl<T: Config> Pallet<T> {
 		<Ledger<T>>::remove(&controller);
 
 		<Payee<T>>::remove(stash);
+		<CompoundPolicies<T>>::remove(stash);
 		Self::do_remove_validator(stash);
 		Self::do_remove_nominator(stash);
 
@@ -727,6 +864,8 @@ impl<T: Config> Pallet<T> {
 		<ErasRewardPoints<T>>::remove(era_index);
 		<ErasTotalStake<T>>::remove(era_index);
 		ErasStartSessionIndex::<T>::remove(era_index);
+		#[allow(deprecated)]
+		<RewardHistory<T>>::remove_prefix(era_index, None);
 	}
 
 	/// Apply previously-unapplied slashes on the beginning of a new era, after a delay.
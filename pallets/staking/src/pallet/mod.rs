@@ -23,9 +23,8 @@ use frame_election_provider_support::{
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency, Defensive, DefensiveResult, DefensiveSaturating, EnsureOrigin,
-		EstimateNextNewSession, Get, LockIdentifier, LockableCurrency, OnUnbalanced, TryCollect,
-		UnixTime,
+		Currency, DefensiveResult, DefensiveSaturating, EnsureOrigin, EstimateNextNewSession, Get,
+		LockIdentifier, LockableCurrency, OnUnbalanced, TryCollect, UnixTime,
 	},
 	weights::Weight,
 	BoundedVec,
@@ -43,8 +42,9 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
-	EraRewardPoints, Exposure, Forcing, NegativeImbalanceOf, Nominations, PositiveImbalanceOf,
+	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, CompoundPolicy,
+	DelegatedOperation, DelegatedOperations, Delegation, EraPayout, EraRewardPoints,
+	EraRewardRecord, Exposure, Forcing, NegativeImbalanceOf, Nominations, PositiveImbalanceOf,
 	RewardDestination, SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk,
 	ValidatorPrefs,
 };
@@ -65,7 +65,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(13);
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(15);
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -267,6 +267,12 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxUnlockingChunks: Get<u32>;
 
+		/// The maximum number of era rewards paid out to auto-compounding stashes in a block.
+		///
+		/// Stashes are compounded after every era, over as many blocks as this takes.
+		#[pallet::constant]
+		type MaxAutoCompoundsPerBlock: Get<u32>;
+
 		/// A hook called when any staker is slashed. Mostly likely this can be a no-op unless
 		/// other pallets exist that are affected by slashing per-staker.
 		type OnStakerSlash: OnStakerSlash<Self::AccountId, BalanceOf<Self>>;
@@ -586,7 +592,8 @@ pub mod pallet {
 
 	/// Rewrds for the account, contains (total_rewards, paid_rewards, Vec<(unpaid_era_index, unpaid_rewards_at_index)>)
 	///
-	/// Update at era_payout and rewards_payout
+	/// Update at era_payout and rewards_payout. `total_rewards` is counted since storage version
+	/// 15, whose migration backfilled it with the rewards paid and unpaid at the time.
 	#[pallet::storage]
 	#[pallet::getter(fn rewards_info_for_account)]
 	pub type RewardsInfoForAccount<T: Config> = StorageMap<
@@ -597,11 +604,58 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The compounding policy of stashes with `RewardDestination::AutoCompound`.
+	///
+	/// Kept in sync with `Payee`: a stash has an entry here if and only if its payee is
+	/// `AutoCompound`.
+	#[pallet::storage]
+	#[pallet::getter(fn compound_policies)]
+	pub type CompoundPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CompoundPolicy<BalanceOf<T>>, OptionQuery>;
+
+	/// The raw key of the last stash in `CompoundPolicies` compounded since the last era ended.
+	///
+	/// Empty when the era just ended, and removed once every stash was visited.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type CompoundCursor<T> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Per-era reward history of an account, split into commission and staking rewards, along
+	/// with what was paid out and where.
+	///
+	/// Unlike `RewardsInfoForAccount`, claimed eras are kept here so that accounting does not
+	/// need to replay events. Eras are cleared after `HistoryDepth`, like the other era
+	/// information.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_history)]
+	pub type RewardHistory<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		T::AccountId,
+		EraRewardRecord<T::AccountId, BalanceOf<T>>,
+		ValueQuery,
+	>;
+
 	/// The reward release coefficient is used to control the number of rewards issued. If v.1 is 0, there is no reward.
 	#[pallet::storage]
 	#[pallet::getter(fn rewards_ratio)]
 	pub type RewardsRatio<T: Config> = StorageValue<_, (u128, u128), ValueQuery>;
 
+	/// Scoped delegations, keyed by the delegate account.
+	///
+	/// A delegate acts for exactly one stash, the same way a controller does.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Delegation<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -736,6 +790,19 @@ pub mod pallet {
 		EraRewardInfo(EraIndex, T::AccountId, BalanceOf<T>),
 		/// The era payout to account [validator, account, commission, staking].
 		EraRewardInfoEx(EraIndex, T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// A stash has delegated some operations to another account.
+		DelegationAdded {
+			stash: T::AccountId,
+			delegate: T::AccountId,
+			operations: DelegatedOperations,
+			expiry: Option<BlockNumberFor<T>>,
+		},
+		/// A delegation has been revoked.
+		DelegationRemoved { stash: T::AccountId, delegate: T::AccountId },
+		/// A stash now compounds its rewards automatically.
+		AutoCompoundSet { stash: T::AccountId, threshold: BalanceOf<T>, stake: Perbill },
+		/// Compounding the reward of `era` failed, and the stash is skipped until the next era.
+		AutoCompoundFailed { stash: T::AccountId, era: EraIndex },
 	}
 
 	#[pallet::error]
@@ -796,13 +863,17 @@ pub mod pallet {
 		BoundNotMet,
 		/// Provided reward destination is not allowed.
 		RewardDestinationRestricted,
+		/// The delegation does not cover this operation or has expired.
+		DelegationNotPermitted,
+		/// No delegation exists for this account.
+		NotDelegate,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-			// just return the weight of the on_finalize.
-			T::DbWeight::get().reads(1)
+			// the weight of the on_finalize, plus the rewards compounded in this block.
+			T::DbWeight::get().reads(1).saturating_add(Self::auto_compound_rewards())
 		}
 
 		fn on_finalize(n: BlockNumberFor<T>) {
@@ -900,7 +971,7 @@ pub mod pallet {
 			// You're auto-bonded forever, here. We might improve this by only bonding when
 			// you actually validate/nominate and remove once you unbond __everything__.
 			<Bonded<T>>::insert(&stash, &controller);
-			<Payee<T>>::insert(&stash, payee.clone());
+			Self::do_set_payee(&stash, payee.clone());
 			let current_era = CurrentEra::<T>::get().unwrap_or(0);
 			let history_depth = T::HistoryDepth::get();
 			let last_reward_era = current_era.saturating_sub(history_depth);
@@ -960,13 +1031,8 @@ pub mod pallet {
 					Error::<T>::InsufficientBond
 				);
 
-				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+				// NOTE: this also updates the staker in the sorted list, if they exist in it.
 				Self::update_ledger(&controller, &ledger);
-				// update this staker in the sorted list, if they exist in it.
-				if T::VoterList::contains(&stash) {
-					let _ =
-						T::VoterList::on_update(&stash, Self::weight_of(&ledger.stash)).defensive();
-				}
 
 				Self::deposit_event(Event::<T>::BondExtra(stash, extra));
 			}
@@ -1061,15 +1127,9 @@ pub mod pallet {
 						.try_push(UnlockChunk { value, era })
 						.map_err(|_| Error::<T>::NoMoreChunks)?;
 				};
-				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+				// NOTE: this also updates the staker in the sorted list, if they exist in it.
 				Self::update_ledger(&controller, &ledger);
 
-				// update this staker in the sorted list, if they exist in it.
-				if T::VoterList::contains(&ledger.stash) {
-					let _ = T::VoterList::on_update(&ledger.stash, Self::weight_of(&ledger.stash))
-						.defensive();
-				}
-
 				Self::deposit_event(Event::<T>::Unbonded(ledger.stash, value));
 			}
 
@@ -1112,13 +1172,14 @@ pub mod pallet {
 		///
 		/// Effects will be felt at the beginning of the next era.
 		///
-		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash,
+		/// or by a delegate holding [`DelegatedOperation::Validate`].
 		#[pallet::call_index(4)]
 		#[pallet::weight(T::WeightInfo::validate())]
 		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
-			let controller = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
 
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::operating_ledger(&who, DelegatedOperation::Validate)?;
 
 			ensure!(ledger.active >= MinValidatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
@@ -1216,7 +1277,8 @@ pub mod pallet {
 		///
 		/// Effects will be felt at the beginning of the next era.
 		///
-		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash,
+		/// or by a delegate holding [`DelegatedOperation::SessionKeys`].
 		///
 		/// ## Complexity
 		/// - Independent of the arguments. Insignificant complexity.
@@ -1225,8 +1287,8 @@ pub mod pallet {
 		#[pallet::call_index(6)]
 		#[pallet::weight(T::WeightInfo::chill())]
 		pub fn chill(origin: OriginFor<T>) -> DispatchResult {
-			let controller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let who = ensure_signed(origin)?;
+			let ledger = Self::operating_ledger(&who, DelegatedOperation::SessionKeys)?;
 			Self::chill_stash(&ledger.stash);
 			Ok(())
 		}
@@ -1251,8 +1313,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
-			let stash = &ledger.stash;
-			<Payee<T>>::insert(stash, payee.clone());
+			Self::do_set_payee(&ledger.stash, payee.clone());
 			Self::deposit_event(Event::<T>::ChangePayee(controller, payee));
 			Ok(())
 		}
@@ -1499,11 +1560,20 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(1))]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
-			_validator_stash: T::AccountId,
+			validator_stash: T::AccountId,
 			era: EraIndex,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			Self::do_payout_stakers(sender, era)
+			// A delegate holding `Payout` claims for the stash that granted it, everyone else
+			// claims for themselves.
+			let stash = if validator_stash != sender && Delegations::<T>::contains_key(&sender) {
+				let ledger = Self::delegated_ledger(&sender, DelegatedOperation::Payout)?;
+				ensure!(ledger.stash == validator_stash, Error::<T>::DelegationNotPermitted);
+				validator_stash
+			} else {
+				sender
+			};
+			Self::do_payout_stakers(stash, era)
 		}
 
 		/// Rebond a portion of the stash scheduled to be unlocked.
@@ -1533,12 +1603,8 @@ pub mod pallet {
 				amount: rebonded_value,
 			});
 
-			// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+			// NOTE: this also updates the staker in the sorted list, if they exist in it.
 			Self::update_ledger(&controller, &ledger);
-			if T::VoterList::contains(&ledger.stash) {
-				let _ = T::VoterList::on_update(&ledger.stash, Self::weight_of(&ledger.stash))
-					.defensive();
-			}
 
 			let removed_chunks = 1u32 // for the case where the last iterated chunk is not removed
 				.saturating_add(initial_unlocking)
@@ -1814,6 +1880,95 @@ pub mod pallet {
             <RewardsRatio<T>>::put(new_ratio);
             Ok(().into())
         }
+
+		/// Allow `delegate` to perform `operations` on behalf of the origin stash until `expiry`.
+		///
+		/// The delegate must not already be a stash, a controller, or a delegate of another
+		/// stash. Calling this again for the same delegate replaces the previous grant.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash.
+		///
+		/// Emits `DelegationAdded`.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 1))]
+		pub fn add_delegation(
+			origin: OriginFor<T>,
+			delegate: AccountIdLookupOf<T>,
+			mut operations: DelegatedOperations,
+			expiry: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			ensure!(<Bonded<T>>::contains_key(&stash), Error::<T>::NotStash);
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			if <Bonded<T>>::contains_key(&delegate) || <Ledger<T>>::contains_key(&delegate) {
+				return Err(Error::<T>::AlreadyPaired.into())
+			}
+			if let Some(existing) = Delegations::<T>::get(&delegate) {
+				ensure!(existing.stash == stash, Error::<T>::AlreadyPaired);
+			}
+			ensure!(!operations.is_empty(), Error::<T>::DelegationNotPermitted);
+
+			let mut seen = Vec::with_capacity(operations.len());
+			operations.retain(|op| {
+				let first = !seen.contains(op);
+				seen.push(*op);
+				first
+			});
+			Delegations::<T>::insert(
+				&delegate,
+				Delegation { stash: stash.clone(), operations: operations.clone(), expiry },
+			);
+			Self::deposit_event(Event::<T>::DelegationAdded { stash, delegate, operations, expiry });
+			Ok(())
+		}
+
+		/// Revoke a delegation previously granted with `add_delegation`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash that granted it. The
+		/// stash does not need to still be bonded.
+		///
+		/// Emits `DelegationRemoved`.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_delegation(
+			origin: OriginFor<T>,
+			delegate: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			let delegation = Delegations::<T>::get(&delegate).ok_or(Error::<T>::NotDelegate)?;
+			ensure!(delegation.stash == stash, Error::<T>::NotDelegate);
+
+			Delegations::<T>::remove(&delegate);
+			Self::deposit_event(Event::<T>::DelegationRemoved { stash, delegate });
+			Ok(())
+		}
+
+		/// Compound rewards automatically, with the given policy.
+		///
+		/// Sets the reward destination to `RewardDestination::AutoCompound`. At the end of each
+		/// era, once the unpaid rewards of the stash reach `threshold`, they are paid out into
+		/// the stash and `stake` of them is added to the bond.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		///
+		/// Emits `AutoCompoundSet`.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+		pub fn set_auto_compound(
+			origin: OriginFor<T>,
+			threshold: BalanceOf<T>,
+			stake: Perbill,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let stash = ledger.stash;
+			<CompoundPolicies<T>>::insert(&stash, CompoundPolicy { threshold, stake });
+			Self::do_set_payee(&stash, RewardDestination::AutoCompound);
+			Self::deposit_event(Event::<T>::AutoCompoundSet { stash, threshold, stake });
+			Ok(())
+		}
 	}
 }
 
@@ -5204,6 +5204,31 @@ mod sorted_list_provider {
 			assert_eq!(<Test as Config>::VoterList::iter().collect::<Vec<_>>(), vec![11, 21, 31]);
 		});
 	}
+
+	#[test]
+	fn slash_updates_voter_list_score() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(
+				<Test as Config>::VoterList::get_score(&11).unwrap(),
+				Staking::weight_of(&11)
+			);
+
+			on_offence_now(
+				&[OffenceDetails {
+					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					reporters: vec![],
+				}],
+				&[Perbill::from_percent(50)],
+			);
+
+			// the score follows the slashed ledger.
+			assert_eq!(Staking::ledger(&10).unwrap().active, 500);
+			assert_eq!(
+				<Test as Config>::VoterList::get_score(&11).unwrap(),
+				Staking::weight_of(&11)
+			);
+		});
+	}
 }
 
 #[test]
@@ -5848,3 +5873,369 @@ mod staking_interface {
 		})
 	}
 }
+
+mod delegation {
+	use super::*;
+
+	#[test]
+	fn add_and_remove_delegation_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// only a stash can delegate.
+			assert_noop!(
+				Staking::add_delegation(
+					RuntimeOrigin::signed(10),
+					5,
+					bounded_vec![DelegatedOperation::Validate],
+					None
+				),
+				Error::<Test>::NotStash
+			);
+			// the delegate can't be a stash or a controller.
+			assert_noop!(
+				Staking::add_delegation(
+					RuntimeOrigin::signed(11),
+					20,
+					bounded_vec![DelegatedOperation::Validate],
+					None
+				),
+				Error::<Test>::AlreadyPaired
+			);
+
+			assert_ok!(Staking::add_delegation(
+				RuntimeOrigin::signed(11),
+				5,
+				bounded_vec![DelegatedOperation::Validate, DelegatedOperation::Validate],
+				Some(100)
+			));
+			assert_eq!(
+				Staking::delegations(5),
+				Some(Delegation {
+					stash: 11,
+					operations: bounded_vec![DelegatedOperation::Validate],
+					expiry: Some(100)
+				})
+			);
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::DelegationAdded {
+					stash: 11,
+					delegate: 5,
+					operations: bounded_vec![DelegatedOperation::Validate],
+					expiry: Some(100)
+				}
+			);
+
+			// another stash can't take over the delegate.
+			assert_noop!(
+				Staking::add_delegation(
+					RuntimeOrigin::signed(21),
+					5,
+					bounded_vec![DelegatedOperation::Payout],
+					None
+				),
+				Error::<Test>::AlreadyPaired
+			);
+			// nor remove it.
+			assert_noop!(
+				Staking::remove_delegation(RuntimeOrigin::signed(21), 5),
+				Error::<Test>::NotDelegate
+			);
+
+			assert_ok!(Staking::remove_delegation(RuntimeOrigin::signed(11), 5));
+			assert_eq!(Staking::delegations(5), None);
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::DelegationRemoved { stash: 11, delegate: 5 }
+			);
+		});
+	}
+
+	#[test]
+	fn delegated_operations_are_bounded() {
+		let operations = vec![
+			DelegatedOperation::Validate,
+			DelegatedOperation::SessionKeys,
+			DelegatedOperation::Payout,
+			DelegatedOperation::Validate,
+		];
+
+		// `add_delegation` takes at most three operations.
+		let encoded = (28u8, 5u64, &operations[..3], None::<u64>).encode();
+		assert!(Call::<Test>::decode(&mut &encoded[..]).is_ok());
+		let encoded = (28u8, 5u64, operations, None::<u64>).encode();
+		assert!(Call::<Test>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn delegate_can_validate_and_chill() {
+		ExtBuilder::default().build_and_execute(|| {
+			let prefs = ValidatorPrefs { commission: Perbill::from_percent(5), blocked: false };
+
+			// not a delegate yet.
+			assert_noop!(
+				Staking::validate(RuntimeOrigin::signed(5), prefs.clone()),
+				Error::<Test>::NotController
+			);
+
+			assert_ok!(Staking::add_delegation(
+				RuntimeOrigin::signed(11),
+				5,
+				bounded_vec![DelegatedOperation::Validate],
+				None
+			));
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(5), prefs.clone()));
+			assert_eq!(Staking::validators(11), prefs);
+
+			// `Validate` does not cover `chill`.
+			assert_noop!(
+				Staking::chill(RuntimeOrigin::signed(5)),
+				Error::<Test>::DelegationNotPermitted
+			);
+
+			assert_ok!(Staking::add_delegation(
+				RuntimeOrigin::signed(11),
+				5,
+				bounded_vec![DelegatedOperation::SessionKeys],
+				None
+			));
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(5)));
+			assert!(!Validators::<Test>::contains_key(11));
+		});
+	}
+
+	#[test]
+	fn expired_delegation_is_rejected() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Staking::add_delegation(
+				RuntimeOrigin::signed(11),
+				5,
+				bounded_vec![DelegatedOperation::Validate],
+				Some(System::block_number() + 2)
+			));
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(5), Default::default()));
+
+			System::set_block_number(System::block_number() + 2);
+			assert_noop!(
+				Staking::validate(RuntimeOrigin::signed(5), Default::default()),
+				Error::<Test>::DelegationNotPermitted
+			);
+		});
+	}
+
+	#[test]
+	fn delegate_can_payout_for_its_stash_only() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Staking::add_delegation(
+				RuntimeOrigin::signed(11),
+				5,
+				bounded_vec![DelegatedOperation::Payout],
+				None
+			));
+			RewardsInfoForAccount::<Test>::insert(11, (100, 0, vec![(0, 100)]));
+
+			// the delegate can't claim for another stash.
+			assert_noop!(
+				Staking::payout_stakers(RuntimeOrigin::signed(5), 21, 0),
+				Error::<Test>::DelegationNotPermitted
+			);
+
+			assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(5), 11, 0));
+			assert_eq!(Staking::rewards_info_for_account(11), (100, 100, vec![]));
+		});
+	}
+}
+
+mod reward_history {
+	use super::*;
+
+	#[test]
+	fn reward_history_keeps_claimed_eras() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			RewardsRatio::<Test>::put((1, 1));
+			Payee::<Test>::insert(11, RewardDestination::Stash);
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+
+			start_active_era(1);
+
+			let record = Staking::reward_history(0, 11);
+			assert!(record.staking > 0);
+			assert_eq!(record.paid, 0);
+			assert_eq!(record.destination, None);
+			// the nominator of 11 earned its share too.
+			assert!(Staking::reward_history(0, 101).staking > 0);
+
+			let earned = record.commission + record.staking;
+			assert_eq!(Staking::rewards_info_for_account(11).0, earned);
+
+			assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 0));
+			// the unpaid tail is drained, but the history keeps the claimed era.
+			assert!(Staking::rewards_info_for_account(11).2.is_empty());
+			let record = Staking::reward_history(0, 11);
+			assert_eq!(record.paid, earned);
+			assert_eq!(record.destination, Some(RewardDestination::Stash));
+		});
+	}
+
+	#[test]
+	fn reward_history_is_pruned_beyond_history_depth() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			RewardsRatio::<Test>::put((1, 1));
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			start_active_era(1);
+			assert!(RewardHistory::<Test>::contains_key(0, 11));
+
+			let history_depth = HistoryDepth::get();
+			start_active_era(history_depth);
+			assert!(RewardHistory::<Test>::contains_key(0, 11));
+			start_active_era(history_depth + 1);
+			assert!(!RewardHistory::<Test>::contains_key(0, 11));
+		});
+	}
+
+	#[test]
+	fn v15_backfills_reward_totals() {
+		use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+		ExtBuilder::default().build_and_execute(|| {
+			StorageVersion::new(14).put::<Pallet<Test>>();
+			RewardsInfoForAccount::<Test>::insert(11, (0, 10, vec![(1, 5)]));
+			RewardsInfoForAccount::<Test>::insert(21, (40, 10, vec![(1, 5)]));
+
+			crate::migrations::v15::MigrateToV15::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Staking::rewards_info_for_account(11), (15, 10, vec![(1, 5)]));
+			// totals already counted are kept.
+			assert_eq!(Staking::rewards_info_for_account(21), (40, 10, vec![(1, 5)]));
+			assert_eq!(Pallet::<Test>::on_chain_storage_version(), 15);
+		});
+	}
+}
+
+mod auto_compound {
+	use super::*;
+
+	#[test]
+	fn set_payee_keeps_compound_policy_in_sync() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Staking::set_payee(
+				RuntimeOrigin::signed(10),
+				RewardDestination::AutoCompound
+			));
+			assert_eq!(Staking::compound_policies(11), Some(CompoundPolicy::default()));
+
+			assert_ok!(Staking::set_auto_compound(
+				RuntimeOrigin::signed(10),
+				50,
+				Perbill::from_percent(40)
+			));
+			// setting the same destination again keeps the policy.
+			assert_ok!(Staking::set_payee(
+				RuntimeOrigin::signed(10),
+				RewardDestination::AutoCompound
+			));
+			assert_eq!(
+				Staking::compound_policies(11),
+				Some(CompoundPolicy { threshold: 50, stake: Perbill::from_percent(40) })
+			);
+
+			assert_ok!(Staking::set_payee(RuntimeOrigin::signed(10), RewardDestination::Stash));
+			assert_eq!(Staking::compound_policies(11), None);
+		});
+	}
+
+	#[test]
+	fn rewards_are_compounded_once_threshold_is_reached() {
+		ExtBuilder::default().build_and_execute(|| {
+			RewardsRatio::<Test>::put((1, 1));
+			assert_ok!(Staking::set_auto_compound(
+				RuntimeOrigin::signed(10),
+				Balance::MAX,
+				Perbill::from_percent(50)
+			));
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			start_active_era(1);
+
+			// below the threshold, nothing is paid out.
+			let unpaid = Staking::rewards_info_for_account(11).2;
+			assert_eq!(unpaid.len(), 1);
+			let reward = unpaid[0].1;
+			assert!(reward > 0);
+
+			assert_ok!(Staking::set_auto_compound(
+				RuntimeOrigin::signed(10),
+				reward,
+				Perbill::from_percent(50)
+			));
+			let free_before = Balances::free_balance(11);
+			let active_before = Staking::ledger(&10).unwrap().active;
+			start_active_era(2);
+
+			assert!(Staking::rewards_info_for_account(11).2.iter().all(|(era, _)| *era != 0));
+			assert_eq!(Staking::reward_history(0, 11).paid, reward);
+			assert_eq!(Balances::free_balance(11), free_before + reward);
+			assert_eq!(
+				Staking::ledger(&10).unwrap().active,
+				active_before + Perbill::from_percent(50) * reward
+			);
+		});
+	}
+
+	#[test]
+	fn compounding_is_paged_across_blocks() {
+		ExtBuilder::default().build_and_execute(|| {
+			RewardsRatio::<Test>::put((1, 1));
+			MaxAutoCompoundsPerBlock::set(1);
+			for controller in [10, 20] {
+				assert_ok!(Staking::set_auto_compound(
+					RuntimeOrigin::signed(controller),
+					1,
+					Perbill::from_percent(50)
+				));
+			}
+			Pallet::<Test>::reward_by_ids(vec![(11, 1), (21, 1)]);
+			start_active_era(1);
+
+			// one stash is compounded in the block the era ended.
+			let unpaid = || {
+				[11, 21]
+					.into_iter()
+					.filter(|stash| !Staking::rewards_info_for_account(stash).2.is_empty())
+					.count()
+			};
+			assert_eq!(unpaid(), 1);
+			assert!(CompoundCursor::<Test>::exists());
+
+			// and the other one in the next block.
+			run_to_block(System::block_number() + 1);
+			assert_eq!(unpaid(), 0);
+			run_to_block(System::block_number() + 1);
+			assert!(!CompoundCursor::<Test>::exists());
+		});
+	}
+
+	#[test]
+	fn failed_payouts_do_not_stall_compounding() {
+		ExtBuilder::default().build_and_execute(|| {
+			MaxAutoCompoundsPerBlock::set(1);
+			for controller in [10, 20] {
+				assert_ok!(Staking::set_auto_compound(
+					RuntimeOrigin::signed(controller),
+					1,
+					Perbill::from_percent(50)
+				));
+			}
+			// eras which have not happened yet can not be paid out.
+			RewardsInfoForAccount::<Test>::insert(11, (20, 0, vec![(5, 10), (6, 10)]));
+			RewardsInfoForAccount::<Test>::insert(21, (10, 0, vec![(0, 10)]));
+			CompoundCursor::<Test>::put(Vec::<u8>::new());
+
+			for _ in 0..3 {
+				Staking::auto_compound_rewards();
+			}
+			assert!(!CompoundCursor::<Test>::exists());
+			assert!(Staking::rewards_info_for_account(21).2.is_empty());
+			assert_eq!(Staking::rewards_info_for_account(11).2.len(), 2);
+			assert!(staking_events().contains(&Event::AutoCompoundFailed { stash: 11, era: 5 }));
+		});
+	}
+}
@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod voter_bags;
+
 pub mod currency {
 	use bp_core::Balance;
 
@@ -0,0 +1,220 @@
+//! Autogenerated voter bag thresholds.
+//!
+//! Arguments
+//! Total issuance: 1_000_000_000 TAKER
+//! Minimum balance: 1 TAKER
+//! for the taker runtimes.
+
+/// Existential weight for the taker runtimes.
+#[cfg(any(test, feature = "std"))]
+#[allow(unused)]
+pub const EXISTENTIAL_WEIGHT: u64 = 18_446_744_073;
+
+/// Constant ratio between bags for the taker runtimes.
+#[cfg(any(test, feature = "std"))]
+#[allow(unused)]
+pub const CONSTANT_RATIO: f64 = 1.1097524964122865;
+
+/// Upper thresholds delimiting the bag list.
+pub const THRESHOLDS: [u64; 200] = [
+	18_446_744_073,
+	20_471_320_286,
+	22_718_098_792,
+	25_211_466_848,
+	27_978_488_273,
+	31_049_197_207,
+	34_456_924_112,
+	38_238_657_552,
+	42_435_445_678,
+	47_092_841_778,
+	52_261_398_726,
+	57_997_217_702,
+	64_362_557_130,
+	71_426_508_450,
+	79_265_746_062,
+	87_965_359_572,
+	97_619_777_383,
+	108_333_791_650,
+	120_223_695_729,
+	133_418_546_463,
+	148_061_565_005,
+	164_311_691_387,
+	182_345_309_706,
+	202_358_162_655,
+	224_567_476_176,
+	249_214_317_299,
+	276_566_210_764,
+	306_920_042_819,
+	340_605_283_717,
+	377_987_563_896,
+	419_472_642_646,
+	465_510_812_353,
+	516_601_786_116,
+	573_300_121_793,
+	636_221_241_353,
+	706_048_110_862,
+	783_538_653_616,
+	869_533_976_886,
+	964_967_501_565,
+	1_070_875_093_818,
+	1_188_406_308_710,
+	1_318_836_867_843,
+	1_463_582_506_449,
+	1_624_214_340_237,
+	1_802_475_918_787,
+	2_000_302_150_597,
+	2_219_840_305_204,
+	2_463_473_320_337,
+	2_733_845_667_089,
+	3_033_892_053_858,
+	3_366_869_280_614,
+	3_736_391_589_255,
+	4_146_469_893_750,
+	4_601_555_315_887,
+	5_106_587_499_185,
+	5_667_048_225_368,
+	6_289_020_915_391,
+	6_979_256_660_844,
+	7_745_247_502_474,
+	8_595_307_751_202,
+	9_538_664_234_328,
+	10_585_556_446_484,
+	11_747_347_692_399,
+	13_036_648_427_863,
+	14_467_453_137_670,
+	16_055_292_236_257,
+	17_817_400_639_815,
+	19_772_904_839_613,
+	21_943_030_507_083,
+	24_351_332_884_086,
+	27_023_952_459_081,
+	29_989_898_704_392,
+	33_281_364_954_351,
+	36_934_077_842_099,
+	40_987_685_087_955,
+	45_486_185_848_519,
+	50_478_408_297_667,
+	56_018_539_623_255,
+	62_166_714_192_278,
+	68_989_666_268_630,
+	76_561_454_368_263,
+	84_964_265_114_135,
+	94_289_305_316_247,
+	104_637_791_959_685,
+	116_122_050_846_330,
+	128_866_735_815_229,
+	143_010_181_775_453,
+	158_705_906_237_684,
+	176_124_275_642_644,
+	195_454_354_573_230,
+	216_905_957_922_294,
+	240_711_928_290_964,
+	267_130_663_337_113,
+	296_448_920_506_631,
+	328_984_929_590_961,
+	365_091_846_895_589,
+	405_161_588_512_152,
+	449_629_084_301_728,
+	498_976_998_763_413,
+	553_740_970_030_008,
+	614_515_423_856_563,
+	681_960_025_708_675,
+	756_806_840_983_589,
+	839_868_281_083_434,
+	932_045_921_589_837,
+	1_034_340_288_255_212,
+	1_147_861_717_031_026,
+	1_273_842_406_011_275,
+	1_413_649_790_106_846,
+	1_568_801_383_623_777,
+	1_740_981_251_851_536,
+	1_932_058_290_449_230,
+	2_144_106_511_040_088,
+	2_379_427_553_200_576,
+	2_640_575_667_196_518,
+	2_930_385_438_636_875,
+	3_252_002_555_977_485,
+	3_608_917_954_835_151,
+	4_005_005_709_725_432,
+	4_444_565_084_513_260,
+	4_932_367_198_005_476,
+	5_473_706_811_208_652,
+	6_074_459_798_367_738,
+	6_741_146_925_594_672,
+	7_481_004_629_360_698,
+	8_302_063_563_104_906,
+	9_213_235_764_529_152,
+	10_224_411_389_721_188,
+	11_346_566_064_089_304,
+	12_591_880_015_330_036,
+	13_973_870_281_536_488,
+	15_507_537_429_476_578,
+	17_209_528_375_568_604,
+	19_098_317_076_865_340,
+	21_194_405_053_324_712,
+	23_520_543_917_900_280,
+	26_101_982_329_864_656,
+	28_966_740_051_876_692,
+	32_145_912_085_495_924,
+	35_674_006_186_328_992,
+	39_589_317_422_305_952,
+	43_934_343_840_662_456,
+	48_756_247_755_410_920,
+	54_107_367_662_263_208,
+	60_045_786_337_494_016,
+	66_635_961_287_072_744,
+	73_949_424_389_161_456,
+	82_065_558_324_123_552,
+	91_072_458_219_664_208,
+	101_067_887_863_676_016,
+	112_160_340_863_831_488,
+	124_470_218_272_089_984,
+	138_131_135_456_434_048,
+	153_291_372_405_041_376,
+	170_115_483_204_960_160,
+	188_786_082_165_086_912,
+	209_505_825_970_600_224,
+	232_499_613_383_791_648,
+	258_017_026_367_554_240,
+	286_335_039_128_268_064,
+	317_761_024_482_905_216,
+	352_636_090_182_429_760,
+	391_338_781_405_019_584,
+	434_289_189_607_162_560,
+	481_953_512_331_417_472,
+	534_849_113_464_460_224,
+	593_550_138_871_083_008,
+	658_693_748_358_043_648,
+	730_987_031_611_505_408,
+	811_214_683_175_875_072,
+	900_247_519_780_729_344,
+	999_051_932_465_633_664,
+	1_108_700_376_099_255_936,
+	1_230_383_010_149_390_080,
+	1_365_420_617_056_549_120,
+	1_515_278_938_431_310_080,
+	1_681_584_584_685_105_664,
+	1_866_142_690_782_713_856,
+	2_070_956_509_757_658_368,
+	2_298_249_156_664_837_120,
+	2_550_487_738_986_234_880,
+	2_830_410_135_408_902_144,
+	3_141_054_713_640_667_136,
+	3_485_793_309_830_309_888,
+	3_868_367_827_561_433_088,
+	4_292_930_853_677_273_600,
+	4_764_090_731_793_682_432,
+	5_286_961_582_742_675_456,
+	5_867_218_814_884_537_344,
+	6_511_160_726_815_252_480,
+	7_225_776_871_124_864_000,
+	8_018_823_921_248_977_920,
+	8_898_909_864_896_613_376,
+	9_875_587_437_916_940_288,
+	10_959_457_812_766_140_416,
+	12_162_285_667_042_361_344,
+	13_497_126_881_079_631_872,
+	14_978_470_250_671_499_264,
+	16_622_394_753_119_862_784,
+	18_446_744_073_709_551_615,
+];
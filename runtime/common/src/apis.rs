@@ -1,6 +1,64 @@
 #[macro_export]
 macro_rules! impl_common_runtime_apis {
 	{$($custom:tt)*} => {
+		/// Staking information of a nominator, shared by the staking runtime APIs.
+		fn staking_nominator_info(account: &AccountId) -> Option<NominatorInfo> {
+			let target_validators = match Staking::nominators(account) {
+				Some(nomination) => nomination.targets.into_iter().map(|acc| acc.into()).collect(),
+				None => return None,
+			};
+			let ledger_info = match Staking::ledger(account) {
+				Some(ledger) => (ledger.total.saturated_into::<u128>(), ledger.active.saturated_into::<u128>()),
+				None => return None,
+			};
+			let payee = Staking::payee(account);
+			let auto_compound = Staking::compound_policies(account).map(|policy| {
+				pallet_staking::CompoundPolicy {
+					threshold: policy.threshold.saturated_into::<u128>(),
+					stake: policy.stake,
+				}
+			});
+			Some(NominatorInfo {
+				stash_account: account.clone(),
+				target_validators,
+				total_staking: ledger_info.0,
+				active_staking: ledger_info.1,
+				rewrds_destination: payee,
+				auto_compound,
+			})
+		}
+
+		/// Staking information of a validator, shared by the staking runtime APIs.
+		fn staking_validator_info(account: &AccountId) -> Option<ValidatorInfo> {
+			let era_index = match Staking::current_era() {
+				Some(index) => index,
+				None => return None,
+			};
+			let validators = Session::validators();
+			let is_active = validators.contains(account);
+			let (total_staking, owner_staking, commission, can_nominated, nominators) = if is_active {
+				let validator_prefs = Staking::eras_validator_prefs(era_index, account);
+				let exposure = Staking::eras_stakers_clipped(era_index, account);
+				(exposure.total.saturated_into::<u128>(), exposure.own.saturated_into::<u128>(), validator_prefs.commission, !validator_prefs.blocked, exposure.others.len() as u8)
+			} else {
+				let active = match Staking::ledger(account) {
+					Some(ledger) => ledger.active.saturated_into::<u128>(),
+					None => return None,
+				};
+				let validator_prefs = Staking::validators(account);
+				(active, active, validator_prefs.commission, !validator_prefs.blocked, 0)
+			};
+			Some(ValidatorInfo {
+				stash_account: account.clone(),
+				is_active,
+				total_staking,
+				owner_staking,
+				nominators,
+				commission,
+				can_nominated,
+			})
+		}
+
 		impl_runtime_apis! {
 			$($custom)*
 
@@ -619,51 +677,10 @@ macro_rules! impl_common_runtime_apis {
 			}
 			impl pallet_staking_runtime_api::StakingRpcApi<Block> for Runtime {
 				fn nominator_info(account: &AccountId) -> Option<NominatorInfo> {
-					let target_validators = match Staking::nominators(account) {
-						Some(nomination) => nomination.targets.into_iter().map(|acc| acc.into()).collect(),
-						None => return None,
-					};
-					let ledger_info = match Staking::ledger(account) {
-						Some(ledger) => (ledger.total.saturated_into::<u128>(), ledger.active.saturated_into::<u128>()),
-						None => return None,
-					};
-					let payee = Staking::payee(account);
-					Some(NominatorInfo {
-						stash_account: account.clone(),
-						target_validators,
-						total_staking: ledger_info.0,
-						active_staking: ledger_info.1,
-						rewrds_destination: payee
-					})
+					staking_nominator_info(account)
 				}
 				fn validator_info(account: &AccountId) -> Option<ValidatorInfo> {
-					let era_index = match Staking::current_era() {
-						Some(index) => index,
-						None => return None,
-					};
-					let validators = Session::validators();
-					let is_active = validators.contains(account);
-					let (total_staking, owner_staking, commission, can_nominated, nominators) = if is_active {
-						let validator_prefs = Staking::eras_validator_prefs(era_index, account);
-						let exposure = Staking::eras_stakers_clipped(era_index, account);
-						(exposure.total.saturated_into::<u128>(), exposure.own.saturated_into::<u128>(), validator_prefs.commission, !validator_prefs.blocked, exposure.others.len() as u8)
-					} else {
-						let active = match Staking::ledger(account) {
-							Some(ledger) => ledger.active.saturated_into::<u128>(),
-							None => return None,
-						};
-						let validator_prefs = Staking::validators(account);
-						(active, active, validator_prefs.commission, !validator_prefs.blocked, 0)
-					};
-					Some(ValidatorInfo {
-						stash_account: account.clone(),
-						is_active,
-						total_staking,
-						owner_staking,
-						nominators,
-						commission,
-						can_nominated,
-					})
+					staking_validator_info(account)
 				}
 
 				fn get_validator_rewards(account: &AccountId, era_index: u32) -> Option<u128> {
@@ -750,7 +767,195 @@ macro_rules! impl_common_runtime_apis {
 					let union = queued_validators.iter().filter(|acc| !part_union.contains(&acc)).chain(part_union.clone()).collect::<Vec<_>>();
 					union.into_iter().map(|acc| acc.clone()).collect()
 				}
+
+				fn reward_history(account: &AccountId, from_era: u32, to_era: u32) -> AccountRewards {
+					let (total_rewards, paid_rewards, _) = Staking::rewards_info_for_account(account);
+					// Only the last `HistoryDepth` eras are kept.
+					let current_era = Staking::current_era().unwrap_or(0);
+					let depth = <Runtime as pallet_staking::Config>::HistoryDepth::get();
+					let from_era = from_era.max(current_era.saturating_sub(depth));
+					let to_era = to_era.min(current_era);
+					let eras: Vec<EraReward> = (from_era..=to_era)
+						.filter_map(|era| {
+							pallet_staking::RewardHistory::<Runtime>::try_get(era, account)
+								.ok()
+								.map(|record| (era, record))
+						})
+						.map(|(era, record)| {
+							let commission = record.commission.saturated_into::<u128>();
+							let staking = record.staking.saturated_into::<u128>();
+							EraReward {
+								era,
+								earned: commission.saturating_add(staking),
+								commission,
+								staking,
+								paid: record.paid.saturated_into::<u128>(),
+								destination: record.destination,
+							}
+						})
+						.collect();
+					AccountRewards {
+						total_rewards: total_rewards.saturated_into::<u128>(),
+						paid_rewards: paid_rewards.saturated_into::<u128>(),
+						eras,
+					}
+				}
+
+				fn staking_events() -> Vec<StakingEvent> {
+					System::read_events_no_consensus()
+						.filter_map(|record| match record.event {
+							RuntimeEvent::Staking(event) => Some(event),
+							_ => None,
+						})
+						.filter_map(|event| match event {
+							pallet_staking::Event::EraPayout(era, validator_payout, remainder) => Some(StakingEvent::EraPayout {
+								era,
+								validator_payout: validator_payout.saturated_into::<u128>(),
+								remainder: remainder.saturated_into::<u128>(),
+							}),
+							pallet_staking::Event::EraRewardInfoEx(era, validator, account, commission, staking) => Some(StakingEvent::EraReward {
+								era,
+								validator,
+								account,
+								commission: commission.saturated_into::<u128>(),
+								staking: staking.saturated_into::<u128>(),
+							}),
+							pallet_staking::Event::Slashed { staker, amount } => Some(StakingEvent::Slashed {
+								staker,
+								amount: amount.saturated_into::<u128>(),
+							}),
+							pallet_staking::Event::StakersElected => Some(StakingEvent::StakersElected),
+							_ => None,
+						})
+						.collect()
+				}
+
+				fn validators(
+					start: Option<AccountId>,
+					page_size: u32,
+					order: ValidatorOrder,
+				) -> Result<StakingPage<ValidatorInfo>, PageError> {
+					use frame_election_provider_support::SortedListProvider;
+					type Voters = <Runtime as pallet_staking::Config>::VoterList;
+					type Targets = <Runtime as pallet_staking::Config>::TargetList;
+
+					let accounts: Box<dyn Iterator<Item = AccountId>> = match (order, start) {
+						(ValidatorOrder::Stake, None) => Box::new(
+							Voters::iter().filter(|acc| pallet_staking::Validators::<Runtime>::contains_key(acc)),
+						),
+						(ValidatorOrder::Stake, Some(start)) => Box::new(
+							Voters::iter_from(&start)
+								.map_err(|_| PageError::StartNotFound)?
+								.filter(|acc| pallet_staking::Validators::<Runtime>::contains_key(acc)),
+						),
+						(ValidatorOrder::Storage, None) => Targets::iter(),
+						(ValidatorOrder::Storage, Some(start)) => {
+							Targets::iter_from(&start).map_err(|_| PageError::StartNotFound)?
+						},
+					};
+					Ok(pallet_staking_runtime_api::paginate(accounts, page_size, staking_validator_info))
+				}
+
+				fn nominators(
+					validator: Option<AccountId>,
+					start: Option<AccountId>,
+					page_size: u32,
+				) -> Result<StakingPage<NominatorInfo>, PageError> {
+					use frame_election_provider_support::SortedListProvider;
+					type Voters = <Runtime as pallet_staking::Config>::VoterList;
+
+					let voters = match start {
+						None => Voters::iter(),
+						Some(start) => Voters::iter_from(&start).map_err(|_| PageError::StartNotFound)?,
+					};
+					let accounts = voters.filter(|acc| match Staking::nominators(acc) {
+						Some(nominations) => validator.map_or(true, |v| nominations.targets.contains(&v)),
+						None => false,
+					});
+					Ok(pallet_staking_runtime_api::paginate(accounts, page_size, staking_nominator_info))
+				}
     		}
+			impl pallet_asset_currency_runtime_api::AssetCurrencyApi<Block, AccountId, Balance> for Runtime {
+				fn account(who: AccountId) -> pallet_asset_currency::types::AccountData<Balance> {
+					pallet_asset_currency::Account::<Runtime>::get(&who)
+				}
+
+				fn locks(who: AccountId) -> Vec<pallet_asset_currency::types::BalanceLock<Balance>> {
+					AssetCurrency::locks(&who).into_inner()
+				}
+
+				fn issuance() -> (Balance, Balance) {
+					(AssetCurrency::total_issuance(), AssetCurrency::inactive_issuance())
+				}
+
+				fn controllers() -> Vec<AccountId> {
+					AssetCurrency::token_controllers()
+				}
+
+				fn whitelist() -> (Option<AccountId>, Vec<AccountId>) {
+					(AssetCurrency::whitelist_admin(), AssetCurrency::whitelist())
+				}
+			}
+
+			impl pallet_deployer_allowlist_runtime_api::DeployerAllowlistApi<Block> for Runtime {
+				fn can_deploy(deployer: H160) -> bool {
+					DeployerAllowlist::can_deploy(&deployer)
+				}
+
+				fn deployer_of(contract: H160) -> Option<H160> {
+					DeployerAllowlist::deployer_of(contract)
+				}
+			}
+
+			impl pallet_provider_runtime_api::ProviderApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+				fn provider(pid: pallet_provider::types::ProviderId) -> Option<pallet_provider::types::ProviderInfo<AccountId, Balance>> {
+					Provider::providers(pid)
+				}
+
+				fn provider_of(owner: AccountId) -> Option<pallet_provider::types::ProviderId> {
+					Provider::provider_of(&owner)
+				}
+
+				fn providers(
+					start: pallet_provider::types::ProviderId,
+					count: u32,
+				) -> Vec<(pallet_provider::types::ProviderId, pallet_provider::types::ProviderInfo<AccountId, Balance>)> {
+					let count = count.min(pallet_provider_runtime_api::MAX_PAGE_SIZE) as usize;
+					(start..Provider::next_provider_id())
+						.filter_map(|pid| Provider::providers(pid).map(|provider| (pid, provider)))
+						.take(count)
+						.collect()
+				}
+
+				fn devices(
+					pid: pallet_provider::types::ProviderId,
+				) -> Vec<(pallet_provider::types::DeviceId, pallet_provider::types::DeviceInfo<Balance, BlockNumber>)> {
+					Provider::devices_of(pid)
+				}
+			}
+
+			#[cfg(feature = "try-runtime")]
+			impl frame_try_runtime::TryRuntime<Block> for Runtime {
+				fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+					// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
+					// have a backtrace here. If any of the pre/post migration checks fail, we shall stop
+					// right here and right now.
+					let weight = Executive::try_runtime_upgrade(checks).unwrap();
+					(weight, BlockWeights::get().max_block)
+				}
+
+				fn execute_block(
+					block: Block,
+					state_root_check: bool,
+					signature_check: bool,
+					select: frame_try_runtime::TryStateSelect,
+				) -> Weight {
+					// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
+					// have a backtrace here.
+					Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
+				}
+			}
+
 			#[cfg(feature = "runtime-benchmarks")]
 			impl frame_benchmarking::Benchmark<Block> for Runtime {
 				fn benchmark_metadata(extra: bool) -> (
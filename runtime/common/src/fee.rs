@@ -0,0 +1,223 @@
+//! The fee markets shared by the runtimes.
+//!
+//! Substrate fees follow `SlowAdjustingFeeUpdate`: the fee multiplier rises while blocks are
+//! fuller than `TargetBlockFullness` and falls back otherwise, never below the constant fees
+//! charged before fees were adjusted. The EVM base fee follows `pallet_base_fee`, whose
+//! elasticity `Root` tunes with `BaseFee::set_elasticity`. Chains started before it defaulted to
+//! `DefaultElasticity` keep a zero elasticity, so a constant base fee, until it is set.
+
+use core::marker::PhantomData;
+use frame_support::{
+	parameter_types,
+	traits::{Get, PreInherents},
+};
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use sp_runtime::{
+	traits::{Bounded, One},
+	FixedPointNumber, Permill, Perquintill,
+};
+
+parameter_types! {
+	/// The fullness of the normal dispatch class the Substrate fees are adjusted towards.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the fee multiplier follows the block fullness.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(75, 1_000_000);
+	/// The fee multiplier never drops below the constant fees charged before.
+	pub MinimumMultiplier: Multiplier = Multiplier::one();
+	pub MaximumMultiplier: Multiplier = Bounded::max_value();
+	/// The EVM base fee moves by up to 12.5% per block, as on Ethereum.
+	pub DefaultElasticity: Permill = Permill::from_parts(125_000);
+}
+
+/// Adjusts the Substrate fees to the fullness of the blocks, slowly.
+pub type SlowAdjustingFeeUpdate<R> = TargetedFeeAdjustment<
+	R,
+	TargetBlockFullness,
+	AdjustmentVariable,
+	MinimumMultiplier,
+	MaximumMultiplier,
+>;
+
+/// Raises the EVM base fee while blocks are more than half full, and lowers it otherwise.
+pub struct BaseFeeThreshold;
+impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
+	fn lower() -> Permill {
+		Permill::zero()
+	}
+	fn ideal() -> Permill {
+		Permill::from_parts(500_000)
+	}
+	fn upper() -> Permill {
+		Permill::from_parts(1_000_000)
+	}
+}
+
+/// Raises the EVM base fee back to the default base fee of `R` at the start of every block, when
+/// empty blocks lowered it below. It could otherwise decay to a few wei, from where it no longer
+/// rises.
+pub struct BaseFeeFloor<R>(PhantomData<R>);
+impl<R: pallet_base_fee::Config> PreInherents for BaseFeeFloor<R> {
+	fn pre_inherents() {
+		let floor = <R as pallet_base_fee::Config>::DefaultBaseFeePerGas::get();
+		pallet_base_fee::BaseFeePerGas::<R>::mutate(|base_fee| *base_fee = (*base_fee).max(floor));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		assert_noop, assert_ok, derive_impl,
+		dispatch::DispatchClass,
+		traits::{ConstU8, IntegrityTest, OnFinalize},
+		weights::{constants::WEIGHT_REF_TIME_PER_SECOND, IdentityFee, Weight},
+	};
+	use sp_core::U256;
+	use sp_runtime::{BuildStorage, DispatchError, Perbill};
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test {
+			System: frame_system,
+			Balances: pallet_balances,
+			TransactionPayment: pallet_transaction_payment,
+			BaseFee: pallet_base_fee,
+		}
+	);
+
+	parameter_types! {
+		pub BlockWeights: frame_system::limits::BlockWeights =
+			frame_system::limits::BlockWeights::with_sensible_defaults(
+				Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 2, u64::MAX),
+				Perbill::from_percent(75),
+			);
+		pub DefaultBaseFeePerGas: U256 = U256::from(100_000_000);
+	}
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type BlockWeights = BlockWeights;
+		type AccountData = pallet_balances::AccountData<u64>;
+		type PreInherents = BaseFeeFloor<Test>;
+	}
+
+	#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+	impl pallet_balances::Config for Test {
+		type AccountStore = System;
+	}
+
+	impl pallet_transaction_payment::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
+		type WeightToFee = IdentityFee<u64>;
+		type LengthToFee = IdentityFee<u64>;
+		type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+		type OperationalFeeMultiplier = ConstU8<5>;
+	}
+
+	impl pallet_base_fee::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Threshold = BaseFeeThreshold;
+		type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+		type DefaultElasticity = DefaultElasticity;
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = RuntimeGenesisConfig::default().build_storage().unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	/// Finalizes a block whose normal dispatch class is `fullness` full and starts the next one.
+	/// Returns the fee multiplier and the base fee the next block charges.
+	fn run_block(fullness: Perbill) -> (Multiplier, U256) {
+		let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+		System::set_block_consumed_resources(fullness * max_normal, 0);
+		TransactionPayment::on_finalize(System::block_number());
+		BaseFee::on_finalize(System::block_number());
+
+		System::set_block_number(System::block_number() + 1);
+		<Test as frame_system::Config>::PreInherents::pre_inherents();
+		(
+			pallet_transaction_payment::NextFeeMultiplier::<Test>::get(),
+			pallet_base_fee::BaseFeePerGas::<Test>::get(),
+		)
+	}
+
+	fn fees() -> (Multiplier, U256) {
+		(
+			pallet_transaction_payment::NextFeeMultiplier::<Test>::get(),
+			pallet_base_fee::BaseFeePerGas::<Test>::get(),
+		)
+	}
+
+	#[test]
+	fn multiplier_recovers_from_its_minimum() {
+		new_test_ext().execute_with(|| TransactionPayment::integrity_test());
+	}
+
+	#[test]
+	fn full_blocks_raise_both_fees() {
+		new_test_ext().execute_with(|| {
+			let (mut multiplier, mut base_fee) = fees();
+			assert_eq!((multiplier, base_fee), (Multiplier::one(), DefaultBaseFeePerGas::get()));
+
+			for _ in 0..100 {
+				let (next_multiplier, next_base_fee) = run_block(Perbill::one());
+				assert!(next_multiplier > multiplier);
+				assert!(next_base_fee > base_fee);
+				(multiplier, base_fee) = (next_multiplier, next_base_fee);
+			}
+		});
+	}
+
+	#[test]
+	fn empty_blocks_keep_both_fees_at_their_floor() {
+		new_test_ext().execute_with(|| {
+			for _ in 0..100 {
+				let fees = run_block(Perbill::zero());
+				assert_eq!(fees, (Multiplier::one(), DefaultBaseFeePerGas::get()));
+			}
+		});
+	}
+
+	#[test]
+	fn empty_blocks_lower_both_fees_back_after_congestion() {
+		new_test_ext().execute_with(|| {
+			for _ in 0..100 {
+				run_block(Perbill::one());
+			}
+
+			let (mut multiplier, mut base_fee) = fees();
+			for _ in 0..1_000 {
+				let (next_multiplier, next_base_fee) = run_block(Perbill::zero());
+				assert!(next_multiplier <= multiplier);
+				assert!(next_base_fee <= base_fee);
+				(multiplier, base_fee) = (next_multiplier, next_base_fee);
+			}
+			assert_eq!((multiplier, base_fee), (Multiplier::one(), DefaultBaseFeePerGas::get()));
+		});
+	}
+
+	#[test]
+	fn root_tunes_the_base_fee_elasticity() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(pallet_base_fee::Elasticity::<Test>::get(), DefaultElasticity::get());
+			assert_noop!(
+				BaseFee::set_elasticity(RuntimeOrigin::signed(1), Permill::zero()),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(BaseFee::set_elasticity(RuntimeOrigin::root(), Permill::zero()));
+			for _ in 0..10 {
+				assert_eq!(run_block(Perbill::one()).1, DefaultBaseFeePerGas::get());
+			}
+
+			assert_ok!(BaseFee::set_elasticity(RuntimeOrigin::root(), Permill::from_percent(50)));
+			assert!(run_block(Perbill::one()).1 > DefaultBaseFeePerGas::get());
+		});
+	}
+}
@@ -1,4 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod apis;
+pub mod fee;
 mod self_contained_call;
@@ -28,7 +28,12 @@ macro_rules! impl_self_contained_call {
 				len: usize,
 			) -> Option<TransactionValidity> {
 				match self {
-					RuntimeCall::Ethereum(call) => call.validate_self_contained(info, dispatch_info, len),
+					RuntimeCall::Ethereum(call) => {
+						if let Err(e) = ensure_can_deploy(call, info) {
+							return Some(Err(e));
+						}
+						call.validate_self_contained(info, dispatch_info, len)
+					},
 					_ => None,
 				}
 			}
@@ -40,7 +45,12 @@ macro_rules! impl_self_contained_call {
 				len: usize,
 			) -> Option<Result<(), TransactionValidityError>> {
 				match self {
-					RuntimeCall::Ethereum(call) => call.pre_dispatch_self_contained(info, dispatch_info, len),
+					RuntimeCall::Ethereum(call) => {
+						if let Err(e) = ensure_can_deploy(call, info) {
+							return Some(Err(e));
+						}
+						call.pre_dispatch_self_contained(info, dispatch_info, len)
+					},
 					_ => None,
 				}
 			}
@@ -50,12 +60,43 @@ macro_rules! impl_self_contained_call {
 				info: Self::SignedInfo,
 			) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
 				match self {
-					call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) => Some(
-						call.dispatch(RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(info))),
-					),
+					RuntimeCall::Ethereum(call @ pallet_ethereum::Call::transact { .. }) => {
+						let target = match transaction_action(&call) {
+							Some(pallet_ethereum::TransactionAction::Call(target)) => Some(target),
+							_ => None,
+						};
+						let origin = RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(info));
+						Some(FeeSharing::with_target(target, || RuntimeCall::Ethereum(call).dispatch(origin)))
+					},
 					_ => None,
 				}
 			}
 		}
+
+		/// Reject Ethereum transactions creating a contract from an account which may not deploy
+		/// contracts.
+		fn ensure_can_deploy(
+			call: &pallet_ethereum::Call<Runtime>,
+			deployer: &H160,
+		) -> Result<(), TransactionValidityError> {
+			if transaction_action(call) == Some(pallet_ethereum::TransactionAction::Create) &&
+				!DeployerAllowlist::can_deploy(deployer)
+			{
+				return Err(sp_runtime::transaction_validity::InvalidTransaction::Call.into());
+			}
+			Ok(())
+		}
+
+		/// Whether an Ethereum transaction creates a contract or calls an account.
+		fn transaction_action(
+			call: &pallet_ethereum::Call<Runtime>,
+		) -> Option<pallet_ethereum::TransactionAction> {
+			let pallet_ethereum::Call::transact { transaction } = call else { return None };
+			Some(match transaction {
+				pallet_ethereum::Transaction::Legacy(t) => t.action,
+				pallet_ethereum::Transaction::EIP2930(t) => t.action,
+				pallet_ethereum::Transaction::EIP1559(t) => t.action,
+			})
+		}
 	};
 }
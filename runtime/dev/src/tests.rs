@@ -0,0 +1,140 @@
+//! Runtime tests of the staking election.
+
+use super::*;
+use frame_support::{assert_ok, traits::Hooks};
+use pallet_election_provider_multi_phase::{
+	CurrentPhase, ElectionCompute, Event as ElectionEvent, Phase, QueuedSolution, Snapshot,
+};
+use pallet_session::SessionManager;
+use pallet_staking::CurrentEra;
+use sp_consensus_babe::Slot;
+
+const VALIDATORS: u8 = 4;
+const STAKE: Balance = 2_000 * UNITS;
+
+fn validators() -> Vec<AccountId> {
+	(1..=VALIDATORS).map(|i| AccountId::from([i; 20])).collect()
+}
+
+fn session_keys(i: u8) -> opaque::SessionKeys {
+	opaque::SessionKeys {
+		babe: sp_core::sr25519::Public::from_raw([i; 32]).into(),
+		grandpa: sp_core::ed25519::Public::from_raw([i; 32]).into(),
+		im_online: sp_core::sr25519::Public::from_raw([i; 32]).into(),
+	}
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let endowed = validators().into_iter().map(|v| (v, 2 * STAKE)).collect::<Vec<_>>();
+	let storage = RuntimeGenesisConfig {
+		balances: BalancesConfig { balances: endowed.clone() },
+		asset_currency: AssetCurrencyConfig { balances: endowed, ..Default::default() },
+		babe: BabeConfig { epoch_config: BABE_GENESIS_EPOCH_CONFIG, ..Default::default() },
+		session: SessionConfig {
+			keys: (1..=VALIDATORS)
+				.map(|i| (AccountId::from([i; 20]), AccountId::from([i; 20]), session_keys(i)))
+				.collect(),
+		},
+		staking: StakingConfig {
+			validator_count: VALIDATORS as u32,
+			minimum_validator_count: 1,
+			stakers: validators()
+				.into_iter()
+				.map(|v| (v, v, STAKE, StakerStatus::Validator))
+				.collect(),
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Runs the election provider up to block `n`. One slot is authored per block, so the session
+/// rotates after `BabeEpochDuration` blocks.
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		pallet_babe::CurrentSlot::<Runtime>::put(Slot::from(next as u64));
+		ElectionProviderMultiPhase::on_initialize(next);
+	}
+}
+
+/// Forces an era and runs through its election phases. Returns the block the era rotates at.
+fn run_to_election() -> BlockNumber {
+	assert_ok!(Staking::force_new_era(RuntimeOrigin::root()));
+	let election = BabeEpochDuration::get() as BlockNumber;
+
+	run_to_block(election - SignedPhase::get() - UnsignedPhase::get() - 1);
+	assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+
+	run_to_block(election - SignedPhase::get() - UnsignedPhase::get());
+	assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Signed);
+	assert!(Snapshot::<Runtime>::get().is_some());
+
+	run_to_block(election - UnsignedPhase::get());
+	assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Unsigned((true, System::block_number())));
+
+	election
+}
+
+/// Plans the session that starts the next era, as the session pallet does when it rotates.
+fn rotate_era() -> Vec<AccountId> {
+	let session = Session::current_index() + 2;
+	let elected = <Staking as SessionManager<AccountId>>::new_session(session)
+		.expect("the era rotates with a new validator set");
+
+	assert_eq!(CurrentEra::<Runtime>::get(), Some(1));
+	assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+	assert!(Snapshot::<Runtime>::get().is_none());
+	elected
+}
+
+fn election_compute() -> Option<ElectionCompute> {
+	System::events().into_iter().find_map(|record| match record.event {
+		RuntimeEvent::ElectionProviderMultiPhase(ElectionEvent::ElectionFinalized {
+			compute,
+			..
+		}) => Some(compute),
+		_ => None,
+	})
+}
+
+#[test]
+fn era_is_elected_from_an_unsigned_solution() {
+	new_test_ext().execute_with(|| {
+		let election = run_to_election();
+
+		let (solution, witness, _) = ElectionProviderMultiPhase::mine_solution().unwrap();
+		assert_ok!(ElectionProviderMultiPhase::submit_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(solution),
+			witness,
+		));
+		assert!(QueuedSolution::<Runtime>::get().is_some());
+
+		run_to_block(election);
+		let mut elected = rotate_era();
+		elected.sort();
+		assert_eq!(elected, validators());
+		assert_eq!(election_compute(), Some(ElectionCompute::Unsigned));
+	});
+}
+
+#[test]
+fn era_falls_back_to_on_chain_election_without_a_solution() {
+	new_test_ext().execute_with(|| {
+		let election = run_to_election();
+		assert!(QueuedSolution::<Runtime>::get().is_none());
+
+		run_to_block(election);
+		let mut elected = rotate_era();
+		elected.sort();
+		assert_eq!(elected, validators());
+		assert_eq!(election_compute(), Some(ElectionCompute::Fallback));
+	});
+}
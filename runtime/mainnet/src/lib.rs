@@ -14,7 +14,7 @@ pub use taker_mainnet_constants::{
 	currency::{GWEI, UNITS as BFC, *},
 	fee::*,
 	time::*,
-	BABE_GENESIS_EPOCH_CONFIG, DEFAULT_ADMIN, FEE_COLLECTOR,
+	voter_bags, BABE_GENESIS_EPOCH_CONFIG, DEFAULT_ADMIN, FEE_COLLECTOR,
 };
 
 use fp_rpc::TransactionStatus;
@@ -47,8 +47,8 @@ use pallet_ethereum::{
 	Call::transact, EthereumBlockHashMapping, PostLogContent, Transaction as EthereumTransaction,
 };
 use pallet_evm::{
-	Account as EVMAccount, EVMCurrencyAdapter, EnsureAddressNever, EnsureAddressRoot,
-	FeeCalculator, GasWeightMapping, IdentityAddressMapping, Runner,
+	Account as EVMAccount, EnsureAddressNever, EnsureAddressRoot, FeeCalculator, GasWeightMapping,
+	IdentityAddressMapping, Runner,
 };
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
@@ -56,13 +56,17 @@ use pallet_grandpa::{
 pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_session::historical as session_historical;
 pub use pallet_staking::StakerStatus;
-pub use pallet_staking_runtime_api::{NominatorInfo, ValidatorInfo};
+pub use pallet_staking_runtime_api::{
+	AccountRewards, EraReward, NominatorInfo, PageError, StakingEvent, StakingPage,
+	ValidatorInfo, ValidatorOrder,
+};
 pub use pallet_timestamp::Call as TimestampCall;
-#[allow(deprecated)]
-use pallet_transaction_payment::CurrencyAdapter;
 use sp_runtime::{PerThing, SaturatedConversion};
 
-use frame_election_provider_support::{onchain, SequentialPhragmen};
+use frame_election_provider_support::{
+	onchain, BalancingConfig, ElectionDataProvider, SequentialPhragmen, VoteWeight,
+};
+use pallet_election_provider_multi_phase::{GeometricDepositBase, SolutionAccuracyOf};
 pub use frame_support::{
 	derive_impl,
 	dispatch::{DispatchClass, GetDispatchInfo},
@@ -84,6 +88,11 @@ pub use frame_support::{
 	ConsensusEngineId, PalletId, StorageValue,
 };
 
+use pallet_fee_distribution::{DistributeFees, DistributeTips};
+use taker_common_runtime::fee::{
+	BaseFeeFloor, BaseFeeThreshold, DefaultElasticity, SlowAdjustingFeeUpdate,
+};
+
 mod precompiles;
 pub use precompiles::TakerPrecompiles;
 pub type Precompiles = TakerPrecompiles<Runtime>;
@@ -107,6 +116,13 @@ pub type SignedExtra = (
 pub type UncheckedExtrinsic =
 	fp_self_contained::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_staking::migrations::v14::MigrateToV14<Runtime>,
+	pallet_staking::migrations::v15::MigrateToV15<Runtime>,
+	pallet_fee_sharing::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -114,6 +130,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -143,13 +160,13 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the authorship interface.
 	authoring_version: 1,
 	// The version of the runtime spec.
-	spec_version: 109,
+	spec_version: 110,
 	// The version of the implementation of the spec.
 	impl_version: 1,
 	// A list of supported runtime APIs along with their versions.
 	apis: RUNTIME_API_VERSIONS,
 	// The version of the interface for handling transactions.
-	transaction_version: 1,
+	transaction_version: 2,
 	// The version of the interface for handling state transitions.
 	state_version: 1,
 };
@@ -180,11 +197,24 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// Only the deployers `DeployerAllowlist` allows may create contracts with `pallet_evm`.
+pub struct BaseFilter;
+impl Contains<RuntimeCall> for BaseFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::EVM(
+				pallet_evm::Call::create { source, .. } | pallet_evm::Call::create2 { source, .. },
+			) => DeployerAllowlist::can_deploy(source),
+			_ => true,
+		}
+	}
+}
+
 /// The System pallet defines the core data types used in a Substrate runtime
 #[derive_impl(frame_system::config_preludes::SolochainDefaultConfig)]
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	/// The block type for the runtime.
 	type Block = Block;
 	/// Block & extrinsics weights: base values and limits.
@@ -215,6 +245,8 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = SS58Prefix;
 	/// The maximum number of consumers allowed on a single account.
 	type MaxConsumers = ConstU32<16>;
+	/// Keeps the EVM base fee from decaying below its default.
+	type PreInherents = BaseFeeFloor<Runtime>;
 }
 
 parameter_types! {
@@ -348,6 +380,12 @@ parameter_types! {
 	pub MaxOnChainElectableTargets: u16 = 1250;
 	pub ElectionBounds: frame_election_provider_support::bounds::ElectionBounds =
 	frame_election_provider_support::bounds::ElectionBoundsBuilder::default().voters_count(MaxElectingVoters::get().into()).build();
+	/// The on-chain fallback only ever sees a snapshot it can solve within a block.
+	pub OnChainElectionBounds: frame_election_provider_support::bounds::ElectionBounds =
+	frame_election_provider_support::bounds::ElectionBoundsBuilder::default()
+		.voters_count(MaxOnChainElectingVoters::get().into())
+		.targets_count(MaxOnChainElectableTargets::get().into())
+		.build();
 }
 
 pub struct OnChainSeqPhragmen;
@@ -357,7 +395,124 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = frame_election_provider_support::weights::SubstrateWeight<Runtime>;
 	type MaxWinners = MaxActiveValidators;
-	type Bounds = ElectionBounds;
+	type Bounds = OnChainElectionBounds;
+}
+
+parameter_types! {
+	// Signed and unsigned phases each take a quarter of the last session of the era.
+	pub SignedPhase: BlockNumber = (BabeEpochDuration::get() / 4) as BlockNumber;
+	pub UnsignedPhase: BlockNumber = (BabeEpochDuration::get() / 4) as BlockNumber;
+
+	// signed config
+	pub const SignedRewardBase: Balance = UNITS;
+	pub const SignedFixedDeposit: Balance = UNITS;
+	pub const SignedDepositIncreaseFactor: Percent = Percent::from_percent(10);
+	pub const SignedDepositByte: Balance = STORAGE_BYTE_FEE;
+
+	// miner configs
+	pub const OffchainRepeat: BlockNumber = 5;
+	pub MultiPhaseUnsignedPriority: frame_support::pallet_prelude::TransactionPriority =
+		StakingUnsignedPriority::get() - 1u64;
+	pub MinerMaxWeight: Weight = BlockWeights::get()
+		.get(DispatchClass::Normal)
+		.max_extrinsic
+		.expect("Normal extrinsics have a weight limit configured; qed")
+		.saturating_sub(BlockExecutionWeight::get());
+	// Solution can occupy 90% of normal block size
+	pub MinerMaxLength: u32 = Perbill::from_rational(9u32, 10) *
+		*BlockLength::get().max.get(DispatchClass::Normal);
+	pub OffchainBalancing: Option<BalancingConfig> =
+		Some(BalancingConfig { iterations: MaxIterations::get() as usize, tolerance: 0 });
+}
+
+frame_election_provider_support::generate_solution_type!(
+	#[compact]
+	pub struct NposSolution16::<
+		VoterIndex = u32,
+		TargetIndex = u16,
+		Accuracy = sp_runtime::PerU16,
+		MaxVoters = MaxElectingVoters,
+	>(16)
+);
+
+impl pallet_election_provider_multi_phase::MinerConfig for Runtime {
+	type AccountId = AccountId;
+	type MaxLength = MinerMaxLength;
+	type MaxWeight = MinerMaxWeight;
+	type Solution = NposSolution16;
+	type MaxVotesPerVoter = <
+		<Self as pallet_election_provider_multi_phase::Config>::DataProvider
+		as
+		ElectionDataProvider
+	>::MaxVotesPerVoter;
+	type MaxWinners = MaxActiveValidators;
+
+	// The unsigned submissions have to respect the weight of the submit_unsigned call, thus their
+	// weight estimate function is wired to this call's weight.
+	fn solution_weight(v: u32, t: u32, a: u32, d: u32) -> Weight {
+		<
+			<Self as pallet_election_provider_multi_phase::Config>::WeightInfo
+			as
+			pallet_election_provider_multi_phase::WeightInfo
+		>::submit_unsigned(v, t, a, d)
+	}
+}
+
+pub struct ElectionProviderBenchmarkConfig;
+impl pallet_election_provider_multi_phase::BenchmarkingConfig for ElectionProviderBenchmarkConfig {
+	const VOTERS: [u32; 2] = [1000, 2000];
+	const TARGETS: [u32; 2] = [500, 1000];
+	const ACTIVE_VOTERS: [u32; 2] = [500, 800];
+	const DESIRED_TARGETS: [u32; 2] = [200, 400];
+	const SNAPSHOT_MAXIMUM_VOTERS: u32 = 1000;
+	const MINER_MAXIMUM_VOTERS: u32 = 1000;
+	const MAXIMUM_TARGETS: u32 = 300;
+}
+
+impl pallet_election_provider_multi_phase::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type EstimateCallFee = TransactionPayment;
+	type SignedPhase = SignedPhase;
+	type UnsignedPhase = UnsignedPhase;
+	type BetterSignedThreshold = ();
+	type OffchainRepeat = OffchainRepeat;
+	type MinerTxPriority = MultiPhaseUnsignedPriority;
+	type MinerConfig = Self;
+	type SignedMaxSubmissions = ConstU32<10>;
+	type SignedRewardBase = SignedRewardBase;
+	type SignedDepositBase =
+		GeometricDepositBase<Balance, SignedFixedDeposit, SignedDepositIncreaseFactor>;
+	type SignedDepositByte = SignedDepositByte;
+	type SignedMaxRefunds = ConstU32<3>;
+	type SignedDepositWeight = ();
+	type SignedMaxWeight = MinerMaxWeight;
+	type SlashHandler = (); // burn slashes
+	type RewardHandler = (); // nothing to do upon rewards
+	type DataProvider = Staking;
+	type Fallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
+	type GovernanceFallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Self>, OffchainBalancing>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxWinners = MaxActiveValidators;
+	type ElectionBounds = ElectionBounds;
+	type BenchmarkingConfig = ElectionProviderBenchmarkConfig;
+	type WeightInfo = pallet_election_provider_multi_phase::weights::SubstrateWeight<Self>;
+}
+
+parameter_types! {
+	pub const BagThresholds: &'static [u64] = &voter_bags::THRESHOLDS;
+}
+
+type VoterBagsListInstance = pallet_bags_list::Instance1;
+impl pallet_bags_list::Config<VoterBagsListInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	/// The voter bags-list is loosely kept up to date, and the real source of truth for the score
+	/// of each node is the staking pallet.
+	type ScoreProvider = Staking;
+	type BagThresholds = BagThresholds;
+	type Score = VoteWeight;
+	type WeightInfo = pallet_bags_list::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_staking::Config for Runtime {
@@ -380,17 +535,18 @@ impl pallet_staking::Config for Runtime {
 	type WeightInfo = ();
 	// type CurrencyBalance = Balance;
 	type CurrencyBalance = <Self as pallet_asset_currency::Config>::Balance;
-	type GenesisElectionProvider = Self::ElectionProvider;
+	type GenesisElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type MaxNominations = MaxNominations;
 	type HistoryDepth = HistoryDepth;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
-	type VoterList = pallet_staking::UseNominatorsAndValidatorsMap<Runtime>;
+	type VoterList = VoterList;
 	type TargetList = pallet_staking::UseValidatorsMap<Runtime>;
 	type MaxUnlockingChunks = ConstU32<32>;
+	type MaxAutoCompoundsPerBlock = ConstU32<64>;
 	type OnStakerSlash = ();
 	type BenchmarkingConfig = StakingBenchmarkingConfig;
-	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
+	type ElectionProvider = ElectionProviderMultiPhase;
 }
 
 parameter_types! {
@@ -448,39 +604,155 @@ impl pallet_asset_currency::Config for Runtime {
 	type PalletId = AssetPalletId;
 }
 
+parameter_types! {
+	pub const ProviderPalletId: PalletId = PalletId(*b"provider");
+	pub const PledgePerDevice: Balance = 1_000 * UNITS;
+	pub const MaxDevices: u32 = 1_000;
+}
+
+impl pallet_provider::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PledgeCurrency = AssetCurrency;
+	type RewardCurrency = Balances;
+	type PalletId = ProviderPalletId;
+	type PledgePerDevice = PledgePerDevice;
+	type MaxDevices = MaxDevices;
+	type RewardOrigin = frame_system::EnsureRoot<AccountId>;
+	type PunishOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slash = ();
+}
+
+parameter_types! {
+	/// Deployment stays open on upgrade, as the factories already deployed could not create
+	/// contracts otherwise. Governance restricts it with `set_open_deployment` once they and the
+	/// other deployers are on the allowlist.
+	pub const OpenDeploymentByDefault: bool = true;
+}
+
+impl pallet_deployer_allowlist::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ManagerOrigin = frame_system::EnsureRoot<AccountId>;
+	type OpenByDefault = OpenDeploymentByDefault;
+}
+
 parameter_types! {
 	pub const TransactionByteFee: Balance = TRANSACTION_BYTE_FEE;
 	pub const FeeCollector: AccountId = FEE_COLLECTOR;
 }
 
-type NegativeImbalanceOf<C, T> =
-	<C as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
-
-pub struct DealWithFees<T, C>(sp_std::marker::PhantomData<(T, C)>);
-impl<C, T> OnUnbalanced<NegativeImbalanceOf<C, T>> for DealWithFees<T, C>
-where
-	T: frame_system::Config + pallet_asset_currency::Config,
-	C: Currency<<T as frame_system::Config>::AccountId>,
-{
-	fn on_nonzero_unbalanced(fees: NegativeImbalanceOf<C, T>) {
-		C::resolve_creating(&<T as pallet_asset_currency::Config>::GasFeeCollector::get(), fees);
-	}
+parameter_types! {
+	/// Every fee goes to `FeeCollector` until the distribution is set.
+	pub const DefaultFeeDistribution: pallet_fee_distribution::FeeDistribution =
+		pallet_fee_distribution::FeeDistribution {
+			burn: Percent::zero(),
+			author: Percent::zero(),
+			collector: Percent::from_percent(100),
+			treasury: Percent::zero(),
+		};
+	/// Every EVM priority fee goes to the block author until the distribution is set.
+	pub const DefaultTipDistribution: pallet_fee_distribution::FeeDistribution =
+		pallet_fee_distribution::FeeDistribution {
+			burn: Percent::zero(),
+			author: Percent::from_percent(100),
+			collector: Percent::zero(),
+			treasury: Percent::zero(),
+		};
+}
+
+impl pallet_fee_distribution::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type FeeCollector = FeeCollector;
+	type DefaultDistribution = DefaultFeeDistribution;
+	type DefaultTipDistribution = DefaultTipDistribution;
+	type DistributionOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 /// Provides the basic logic needed to pay the absolute minimum amount needed for a transaction to
 /// be included.
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees<Runtime, Balances>>;
+	type OnChargeTransaction =
+		pallet_fee_payment::TransactionFeeAdapter<Runtime, DistributeFees<Runtime>>;
 	type WeightToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
-	type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 }
 
+/// Lets accounts pay their Substrate and EVM transaction fees in veTAKER, distributed like TAKER
+/// fees.
+impl pallet_fee_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type NativeCurrency = Balances;
+	type AssetCurrency = AssetCurrency;
+	type OnAssetFees = pallet_fee_distribution::Distribute<Runtime, AssetCurrency>;
+	type OnAssetTips = DistributeTips<Runtime, AssetCurrency>;
+	type RateOrigin = frame_system::EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+	pub const FeeSharingPalletId: PalletId = PalletId(*b"feeshare");
+	/// The share of the EVM fees paid to call a registered contract accrued to its beneficiary.
+	pub const DefaultDeveloperShare: Percent = Percent::from_percent(10);
+}
+
+impl pallet_fee_sharing::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = FeeSharingPalletId;
+	type DefaultShare = DefaultDeveloperShare;
+	type ShareOrigin = frame_system::EnsureRoot<AccountId>;
+	type RegisterOrigin = frame_system::EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+	pub const SubmissionDeposit: Balance = 1_000 * UNITS;
+	pub const MaxProposalsPerAccount: u32 = 1;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	/// How long a vote with a conviction of 1 stays locked after its referendum, doubling with
+	/// every conviction level.
+	pub const VoteLockPeriod: BlockNumber = 7 * DAYS;
+	/// At least 5% of the veTAKER issuance must vote, and 60% of the votes approve, for a
+	/// referendum to pass.
+	pub const MinTurnout: Perbill = Perbill::from_percent(5);
+	pub const MinApproval: Perbill = Perbill::from_percent(60);
+	pub const EnactmentPeriod: BlockNumber = 2 * DAYS;
+	pub const MaxProposalLength: u32 = 16 * 1024;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
+	pub const MaxReferendaEnding: u32 = 16;
+	pub const MaxVotes: u32 = 64;
+}
+
+/// Referenda voted with veTAKER, enacting passed proposals with root origin after
+/// `EnactmentPeriod`. Root, or the canceller it sets, may cancel them until then.
+impl pallet_governance::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = AssetCurrency;
+	type SubmissionDeposit = SubmissionDeposit;
+	type Slash = ();
+	type MaxProposalsPerAccount = MaxProposalsPerAccount;
+	type VotingPeriod = VotingPeriod;
+	type MinTurnout = MinTurnout;
+	type MinApproval = MinApproval;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LockPeriod = VoteLockPeriod;
+	type MaxProposalLength = MaxProposalLength;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxEnding = MaxReferendaEnding;
+	type MaxVotes = MaxVotes;
+	type CancelOrigin = EitherOfDiverse<
+		frame_system::EnsureRoot<AccountId>,
+		pallet_governance::EnsureCanceller<Runtime>,
+	>;
+	type WeightInfo = pallet_governance::weights::SubstrateWeight<Runtime>;
+}
+
 /// The Sudo module allows for a single account (called the "sudo key")
 /// to execute dispatchable functions that require a `Root` call
 /// or designate a new account to replace them as the sudo key.
+/// Once governance is live, a referendum dispatching `Sudo::remove_key` removes the key, after
+/// which `Root` is only reached through referenda.
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -564,11 +836,17 @@ impl pallet_evm::Config for Runtime {
 	type FeeCalculator = FixedGasPrice;
 	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
 	type WeightPerGas = WeightPerGas;
-	type OnChargeTransaction = EVMCurrencyAdapter<Balances, DealWithFees<Runtime, Balances>>;
+	type OnChargeTransaction = pallet_fee_payment::EVMFeeAdapter<
+		Runtime,
+		pallet_fee_sharing::ShareFees<Runtime, DistributeFees<Runtime>>,
+		pallet_fee_sharing::ShareFees<Runtime, DistributeTips<Runtime>>,
+	>;
 	type FindAuthor = EthereumFindAuthor<Babe>;
 	type PrecompilesType = TakerPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
-	type OnCreate = ();
+	type OnCreate = DeployerAllowlist;
+	type CreateOriginFilter = DeployerAllowlist;
+	type CreateInnerOriginFilter = DeployerAllowlist;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type Timestamp = Timestamp;
 	type SuicideQuickClearLimit = ConstU32<0>;
@@ -588,21 +866,8 @@ impl pallet_ethereum::Config for Runtime {
 }
 
 parameter_types! {
+	/// The EVM base fee of new chains, and the floor it never drops below.
 	pub DefaultBaseFeePerGas: U256 = (GWEI / 10).into();
-	pub DefaultElasticity: Permill = Permill::zero();
-}
-
-pub struct BaseFeeThreshold;
-impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
-	fn lower() -> Permill {
-		Permill::zero()
-	}
-	fn ideal() -> Permill {
-		Permill::from_parts(500_000)
-	}
-	fn upper() -> Permill {
-		Permill::from_parts(1_000_000)
-	}
 }
 
 /// The Base fee module adds support for EIP-1559 transactions and handles base fee calculations.
@@ -707,6 +972,30 @@ mod runtime {
 	#[runtime::pallet_index(20)]
 	pub type Offences = pallet_offences;
 
+	#[runtime::pallet_index(21)]
+	pub type ElectionProviderMultiPhase = pallet_election_provider_multi_phase;
+
+	#[runtime::pallet_index(22)]
+	pub type VoterList = pallet_bags_list<Instance1>;
+
+	#[runtime::pallet_index(23)]
+	pub type Provider = pallet_provider;
+
+	#[runtime::pallet_index(24)]
+	pub type DeployerAllowlist = pallet_deployer_allowlist;
+
+	#[runtime::pallet_index(25)]
+	pub type FeePayment = pallet_fee_payment;
+
+	#[runtime::pallet_index(26)]
+	pub type FeeSharing = pallet_fee_sharing;
+
+	#[runtime::pallet_index(27)]
+	pub type FeeDistribution = pallet_fee_distribution;
+
+	#[runtime::pallet_index(28)]
+	pub type Governance = pallet_governance;
+
 	#[runtime::pallet_index(40)]
 	pub type EVM = pallet_evm;
 
@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bp_core::AccountId;
-pub use taker_common_constants::{currency, time};
+pub use taker_common_constants::{currency, time, voter_bags};
 
 pub mod fee {
 	use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
@@ -10,7 +10,10 @@ use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 use pallets_precompile::{
-	asset_currency::AssetCurrencyPrecompile, native_currency::NativeCurrencyPrecompile,
+	asset_currency::AssetCurrencyPrecompile, batch::BatchPrecompile,
+	call_permit::CallPermitPrecompile, fee_sharing::FeeSharingPrecompile,
+	governance::GovernancePrecompile, multisig::MultisigPrecompile,
+	native_currency::NativeCurrencyPrecompile, provider::ProviderPrecompile,
 	staking::StakingPrecompile,
 };
 
@@ -23,7 +26,7 @@ where
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
-	pub fn used_addresses() -> [H160; 14] {
+	pub fn used_addresses() -> [H160; 20] {
 		[
 			hash(1),
 			hash(2),
@@ -39,6 +42,12 @@ where
 			hash(1101),
 			hash(1102),
 			hash(1103),
+			hash(1104),
+			hash(1105),
+			hash(1106),
+			hash(1107),
+			hash(1108),
+			hash(1109),
 		]
 	}
 }
@@ -48,6 +57,12 @@ where
 	AssetCurrencyPrecompile<R>: Precompile,
 	NativeCurrencyPrecompile<R>: Precompile,
 	StakingPrecompile<R>: Precompile,
+	ProviderPrecompile<R>: Precompile,
+	BatchPrecompile<R>: Precompile,
+	MultisigPrecompile<R>: Precompile,
+	CallPermitPrecompile<R>: Precompile,
+	FeeSharingPrecompile<R>: Precompile,
+	GovernancePrecompile<R>: Precompile,
 {
 	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
 		match handle.code_address() {
@@ -68,6 +83,12 @@ where
 			a if a == hash(1101) => Some(AssetCurrencyPrecompile::<R>::execute(handle)),
 			a if a == hash(1102) => Some(NativeCurrencyPrecompile::<R>::execute(handle)),
 			a if a == hash(1103) => Some(StakingPrecompile::<R>::execute(handle)),
+			a if a == hash(1104) => Some(ProviderPrecompile::<R>::execute(handle)),
+			a if a == hash(1105) => Some(BatchPrecompile::<R>::execute(handle)),
+			a if a == hash(1106) => Some(MultisigPrecompile::<R>::execute(handle)),
+			a if a == hash(1107) => Some(CallPermitPrecompile::<R>::execute(handle)),
+			a if a == hash(1108) => Some(FeeSharingPrecompile::<R>::execute(handle)),
+			a if a == hash(1109) => Some(GovernancePrecompile::<R>::execute(handle)),
 			_ => None,
 		}
 	}